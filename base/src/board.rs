@@ -1,8 +1,24 @@
+use crate::error::Location;
+use crate::rule::RuleParameters;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Colors cycled through when rendering sets (X11 color names).
+const SET_COLORS: [&str; 8] = [
+    "lightblue",
+    "lightgreen",
+    "gold",
+    "salmon",
+    "plum",
+    "lightgray",
+    "orange",
+    "khaki",
+];
 
 /// Top-level structure for board definitions.
 /// Each board contains sets (continents), fields (countries), and relations (borders).
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Board {
     /// Unique identifier for this board definition.
     pub id: String,
@@ -26,11 +42,13 @@ pub struct Board {
     pub fields: Vec<FieldElement>,
 
     /// Connections between fields (bidirectional borders).
+    /// Each border is listed once; `(a, b)` and `(b, a)` are the same border.
     pub relations: Vec<(u8, u8)>,
 }
 
 /// Represents a group of fields that share a common theme or bonus (e.g. a continent).
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FieldSet {
     /// Numeric ID (must be unique).
     pub id: u8,
@@ -40,7 +58,8 @@ pub struct FieldSet {
 }
 
 /// Represents a single field (territory or country) on the board.
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FieldElement {
     /// Unique numeric ID.
     pub id: u8,
@@ -61,30 +80,984 @@ pub struct FieldElement {
     pub filename: String,
 }
 
+impl Board {
+    /// Loads and validates a board definition from a RON file.
+    /// I/O, parse and validation failures are distinct [`crate::error::DataError`] variants.
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> crate::error::Result<Self> {
+        crate::loader::load_and_validate_ron(path)
+    }
+
+    /// Returns all fields bordering `id`, sorted and free of duplicates.
+    /// Same as [`Board::neighbors_undirected`], since each border is listed
+    /// only once. Unknown ids yield an empty list.
+    pub fn neighbors(&self, id: u8) -> Vec<u8> {
+        self.neighbors_undirected(id)
+    }
+
+    /// Returns all fields sharing a relation with `id`, regardless of the
+    /// order in which the pair is listed. Each neighbor appears only once.
+    pub fn neighbors_undirected(&self, id: u8) -> Vec<u8> {
+        let mut neighbors: Vec<u8> = self
+            .relations
+            .iter()
+            .filter_map(|&(a, b)| match (a == id, b == id) {
+                (true, _) => Some(b),
+                (_, true) => Some(a),
+                _ => None,
+            })
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors
+    }
+
+    /// Builds an adjacency index over all relations, treating each pair as a
+    /// bidirectional border. Neighbor lists are sorted and free of duplicates.
+    /// Build it once when many neighbor queries are needed.
+    pub fn adjacency(&self) -> HashMap<u8, Vec<u8>> {
+        let mut index: HashMap<u8, Vec<u8>> = HashMap::new();
+        for &(a, b) in &self.relations {
+            index.entry(a).or_default().push(b);
+            index.entry(b).or_default().push(a);
+        }
+        for neighbors in index.values_mut() {
+            neighbors.sort_unstable();
+            neighbors.dedup();
+        }
+        index
+    }
+
+    /// Expands every border into both directions, `(a, b)` and `(b, a)`.
+    /// The result is sorted and free of duplicates; `relations` is left untouched.
+    pub fn symmetric_relations(&self) -> Vec<(u8, u8)> {
+        let mut pairs: Vec<(u8, u8)> = self
+            .relations
+            .iter()
+            .flat_map(|&(a, b)| [(a, b), (b, a)])
+            .collect();
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    /// Builds a dense adjacency matrix over all fields, sorted by id.
+    /// Entry `[i][j]` is `true` iff the relation `(ids[i], ids[j])` is listed;
+    /// with `symmetric` set, the reverse pair counts as well.
+    /// Relations referring to unknown fields are ignored.
+    pub fn adjacency_matrix(&self, symmetric: bool) -> (Vec<u8>, Vec<Vec<bool>>) {
+        let mut ids: Vec<u8> = self.fields.iter().map(|field| field.id).collect();
+        ids.sort_unstable();
+        let index: HashMap<u8, usize> = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        let mut matrix = vec![vec![false; ids.len()]; ids.len()];
+        for &(a, b) in &self.relations {
+            if let (Some(&i), Some(&j)) = (index.get(&a), index.get(&b)) {
+                matrix[i][j] = true;
+                if symmetric {
+                    matrix[j][i] = true;
+                }
+            }
+        }
+        (ids, matrix)
+    }
+
+    /// Counts the bordering fields of every field.
+    /// Fields without borders are included with a count of zero.
+    pub fn border_counts(&self) -> HashMap<u8, usize> {
+        let adjacency = self.adjacency();
+        self.fields
+            .iter()
+            .map(|field| (field.id, adjacency.get(&field.id).map_or(0, Vec::len)))
+            .collect()
+    }
+
+    /// Returns the id(s) of the fields with the most borders, lowest id first.
+    pub fn max_border_field(&self) -> Vec<u8> {
+        self.border_extremes(|counts| counts.values().max().copied())
+    }
+
+    /// Returns the id(s) of the fields with the fewest borders, lowest id first.
+    pub fn min_border_field(&self) -> Vec<u8> {
+        self.border_extremes(|counts| counts.values().min().copied())
+    }
+
+    fn border_extremes(&self, pick: impl Fn(&HashMap<u8, usize>) -> Option<usize>) -> Vec<u8> {
+        let counts = self.border_counts();
+        let Some(target) = pick(&counts) else {
+            return Vec::new();
+        };
+        let mut ids: Vec<u8> = counts
+            .into_iter()
+            .filter(|&(_, count)| count == target)
+            .map(|(id, _)| id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Looks up a field by its id.
+    pub fn field(&self, id: u8) -> Option<&FieldElement> {
+        self.fields.iter().find(|field| field.id == id)
+    }
+
+    /// Returns `true` if a field with the given id exists on the board.
+    pub fn has_field(&self, id: u8) -> bool {
+        self.field(id).is_some()
+    }
+
+    /// Returns all relations whose endpoints belong to the same set.
+    /// Relations referring to unknown fields are skipped.
+    pub fn intra_set_relations(&self) -> Vec<(u8, u8)> {
+        self.relations_by_set(true)
+    }
+
+    /// Returns all relations whose endpoints belong to different sets.
+    /// Relations referring to unknown fields are skipped.
+    pub fn cross_set_relations(&self) -> Vec<(u8, u8)> {
+        self.relations_by_set(false)
+    }
+
+    /// Builds the set adjacency graph: two sets are adjacent if any of their
+    /// fields share a border. Every set is present, possibly without neighbors.
+    pub fn continent_graph(&self) -> HashMap<u8, HashSet<u8>> {
+        let mut graph: HashMap<u8, HashSet<u8>> =
+            self.set_ids().map(|id| (id, HashSet::new())).collect();
+        for (a, b) in self.cross_set_relations() {
+            if let (Some(a), Some(b)) = (self.field(a), self.field(b)) {
+                graph.entry(a.set_id).or_default().insert(b.set_id);
+                graph.entry(b.set_id).or_default().insert(a.set_id);
+            }
+        }
+        graph
+    }
+
+    fn relations_by_set(&self, same_set: bool) -> Vec<(u8, u8)> {
+        self.relations
+            .iter()
+            .copied()
+            .filter(|&(a, b)| match (self.field(a), self.field(b)) {
+                (Some(a), Some(b)) => (a.set_id == b.set_id) == same_set,
+                _ => false,
+            })
+            .collect()
+    }
+
+    /// Bonus for fully holding the given set, looked up in `rules.sets_bonus`
+    /// (the board itself defines no bonuses). Sets without an entry give 0;
+    /// returns `None` if the set is not on this board.
+    pub fn bonus_for_set(&self, set_id: u8, rules: &RuleParameters) -> Option<u32> {
+        self.sets.iter().find(|set| set.id == set_id)?;
+        let bonus = rules
+            .sets_bonus
+            .iter()
+            .find(|&&(id, _)| id == set_id)
+            .map_or(0, |&(_, bonus)| u32::from(bonus));
+        Some(bonus)
+    }
+
+    /// Sum of the bonuses of all sets on this board, for balance checks.
+    /// Entries in `rules.sets_bonus` for sets not on the board are ignored.
+    pub fn total_bonus(&self, rules: &RuleParameters) -> u32 {
+        self.sets
+            .iter()
+            .filter_map(|set| self.bonus_for_set(set.id, rules))
+            .sum()
+    }
+
+    /// Returns all fields belonging to the given set.
+    /// Unknown set ids yield an empty list.
+    pub fn fields_in_set(&self, set_id: u8) -> Vec<&FieldElement> {
+        self.fields
+            .iter()
+            .filter(|field| field.set_id == set_id)
+            .collect()
+    }
+
+    /// Iterates over the ids of all sets, in definition order.
+    pub fn set_ids(&self) -> impl Iterator<Item = u8> + '_ {
+        self.sets.iter().map(|set| set.id)
+    }
+
+    /// Finds a shortest path between two fields using breadth-first search.
+    /// Relations are treated as bidirectional borders.
+    /// The path includes both endpoints; `from == to` yields a single-element path.
+    /// Returns `None` if either field is unknown or `to` is unreachable.
+    pub fn shortest_path(&self, from: u8, to: u8) -> Option<Vec<u8>> {
+        if !self.has_field(from) || !self.has_field(to) {
+            return None;
+        }
+        if from == to {
+            return Some(vec![from]);
+        }
+
+        let adjacency = self.adjacency();
+        let mut previous: HashMap<u8, u8> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(current) = queue.pop_front() {
+            for &next in adjacency.get(&current).into_iter().flatten() {
+                if next == from || previous.contains_key(&next) {
+                    continue;
+                }
+                previous.insert(next, current);
+                if next == to {
+                    let mut path = vec![to];
+                    let mut step = to;
+                    while let Some(&prev) = previous.get(&step) {
+                        path.push(prev);
+                        step = prev;
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(next);
+            }
+        }
+        None
+    }
+
+    /// Number of borders to cross to get from `from` to `to`.
+    /// Returns `None` under the same conditions as [`Board::shortest_path`].
+    pub fn distance(&self, from: u8, to: u8) -> Option<usize> {
+        self.shortest_path(from, to).map(|path| path.len() - 1)
+    }
+
+    /// Splits the board into connected regions, treating relations as bidirectional.
+    /// Fields without any relation form their own singleton component.
+    /// Components are ordered by the first of their fields in `fields`.
+    pub fn connected_components(&self) -> Vec<HashSet<u8>> {
+        let adjacency = self.adjacency();
+        let mut seen = HashSet::new();
+        let mut components = Vec::new();
+        for field in &self.fields {
+            if !seen.insert(field.id) {
+                continue;
+            }
+            let mut component = HashSet::from([field.id]);
+            let mut stack = vec![field.id];
+            while let Some(current) = stack.pop() {
+                for &next in adjacency.get(&current).into_iter().flatten() {
+                    if seen.insert(next) {
+                        component.insert(next);
+                        stack.push(next);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// Returns `true` if every field can be reached from every other field.
+    pub fn is_connected(&self) -> bool {
+        self.connected_components().len() <= 1
+    }
+
+    /// Finds the choke points of the board: fields whose removal splits
+    /// their region into several parts. Relations are treated as bidirectional.
+    /// Uses Tarjan's DFS low-link algorithm.
+    pub fn articulation_points(&self) -> HashSet<u8> {
+        struct Search<'a> {
+            adjacency: &'a HashMap<u8, Vec<u8>>,
+            order: HashMap<u8, usize>,
+            low: HashMap<u8, usize>,
+            points: HashSet<u8>,
+        }
+
+        impl Search<'_> {
+            fn visit(&mut self, node: u8, parent: Option<u8>) {
+                let index = self.order.len();
+                self.order.insert(node, index);
+                self.low.insert(node, index);
+                let mut children = 0;
+                for &next in self.adjacency.get(&node).into_iter().flatten() {
+                    if Some(next) == parent {
+                        continue;
+                    }
+                    if let Some(&seen) = self.order.get(&next) {
+                        let low = self.low[&node].min(seen);
+                        self.low.insert(node, low);
+                        continue;
+                    }
+                    children += 1;
+                    self.visit(next, Some(node));
+                    let low = self.low[&node].min(self.low[&next]);
+                    self.low.insert(node, low);
+                    if parent.is_some() && self.low[&next] >= index {
+                        self.points.insert(node);
+                    }
+                }
+                if parent.is_none() && children > 1 {
+                    self.points.insert(node);
+                }
+            }
+        }
+
+        let adjacency = self.adjacency();
+        let mut search = Search {
+            adjacency: &adjacency,
+            order: HashMap::new(),
+            low: HashMap::new(),
+            points: HashSet::new(),
+        };
+        for field in &self.fields {
+            if !search.order.contains_key(&field.id) {
+                search.visit(field.id, None);
+            }
+        }
+        search.points
+    }
+
+    /// Runs the regular validation and additionally rejects boards
+    /// that are split into several unreachable regions.
+    pub fn validate_strict(&self) -> Result<(), String> {
+        crate::validator::Validatable::validate(self)?;
+        let components = self.connected_components();
+        if components.len() > 1 {
+            return Err(format!(
+                "Board: fields are split into {} disconnected regions",
+                components.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Renders the board as an undirected Graphviz DOT graph.
+    /// Every field becomes a node labeled with its name and filled with the
+    /// color of its set; every relation becomes an edge.
+    pub fn to_dot(&self) -> String {
+        let mut dot = format!("graph \"{}\" {{\n", dot_escape(&self.id));
+        dot.push_str("    node [style=filled];\n");
+        for field in &self.fields {
+            dot.push_str(&format!(
+                "    {} [label=\"{}\", fillcolor=\"{}\"];\n",
+                field.id,
+                dot_escape(&field.name),
+                self.set_color(field.set_id)
+            ));
+        }
+        for &(a, b) in &self.relations {
+            dot.push_str(&format!("    {} -- {};\n", a, b));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Writes the board to a RON file in a canonical order: sets and fields
+    /// sorted by id, every relation as `(smaller, larger)` and the relations
+    /// sorted. Boards with the same content therefore produce identical files.
+    pub fn to_file_sorted(&self, path: impl AsRef<std::path::Path>) -> crate::error::Result<()> {
+        crate::loader::save_ron(&self.normalized(), path)
+    }
+
+    /// Returns a copy in canonical order: sets and fields sorted by id,
+    /// every relation as `(smaller, larger)` and the relations sorted.
+    pub fn normalized(&self) -> Board {
+        let mut board = self.clone();
+        board.sets.sort_by_key(|set| set.id);
+        board.fields.sort_by_key(|field| field.id);
+        for relation in &mut board.relations {
+            *relation = (relation.0.min(relation.1), relation.0.max(relation.1));
+        }
+        board.relations.sort_unstable();
+        board
+    }
+
+    /// Picks a display color for a set, based on its position in `sets`.
+    /// Unknown sets are drawn white.
+    fn set_color(&self, set_id: u8) -> &'static str {
+        self.sets
+            .iter()
+            .position(|set| set.id == set_id)
+            .map_or("white", |index| SET_COLORS[index % SET_COLORS.len()])
+    }
+
+    /// Checks that every field position lies within `[0, width] x [0, height]`.
+    /// Both bounds are inclusive. Kept apart from `validate()` since the
+    /// board image size is only known to the frontend.
+    pub fn validate_bounds(&self, width: i16, height: i16) -> Result<(), String> {
+        for field in &self.fields {
+            let (x, y) = field.position;
+            if !(0..=width).contains(&x) || !(0..=height).contains(&y) {
+                return Err(format!(
+                    "Board: field {} ({}) at ({},{}) lies outside {}x{}",
+                    field.id, field.name, x, y, width, height
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that no two fields overlap, i.e. that their positions differ by
+    /// more than `epsilon` on at least one axis. `epsilon = 0` flags identical positions.
+    /// Compares every pair of fields (O(n²)), which is fine for board-sized inputs.
+    pub fn validate_positions(&self, epsilon: i16) -> Result<(), String> {
+        for (i, first) in self.fields.iter().enumerate() {
+            for second in &self.fields[i + 1..] {
+                let dx = first.position.0.abs_diff(second.position.0);
+                let dy = first.position.1.abs_diff(second.position.1);
+                if dx <= epsilon.unsigned_abs() && dy <= epsilon.unsigned_abs() {
+                    return Err(format!(
+                        "Board: fields {} and {} overlap at ({},{})",
+                        first.id, second.id, first.position.0, first.position.1
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "json")]
+impl Board {
+    /// Serializes the board as compact JSON; tuples become two-element arrays.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Serializes the board as indented JSON.
+    pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Parses a board from JSON and validates it.
+    /// Validation failures are reported as a `serde_json` custom error.
+    pub fn from_json(s: &str) -> Result<Board, serde_json::Error> {
+        let board: Board = serde_json::from_str(s)?;
+        crate::validator::Validatable::validate(&board).map_err(serde::de::Error::custom)?;
+        Ok(board)
+    }
+
+    /// Loads and validates a board from a JSON file.
+    /// Validation failures carry the same field paths as with [`Board::from_file`].
+    pub fn from_json_file(path: impl AsRef<std::path::Path>) -> crate::error::Result<Board> {
+        use crate::error::DataError;
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|source| DataError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let board: Board = serde_json::from_str(&text).map_err(|source| DataError::ParseJson {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        crate::loader::validate(&board)?;
+        Ok(board)
+    }
+}
+
+#[cfg(feature = "svg")]
+impl Board {
+    /// Renders a preview of the board as an SVG image of `width` x `height` pixels.
+    /// Fields are drawn as circles at their `position`, filled with the color
+    /// of their set and labeled with their name; relations are drawn as lines.
+    /// The view box is fitted around all field positions.
+    pub fn to_svg(&self, width: u32, height: u32) -> String {
+        const RADIUS: i32 = 12;
+        const MARGIN: i32 = 4 * RADIUS;
+        let xs = self.fields.iter().map(|field| i32::from(field.position.0));
+        let ys = self.fields.iter().map(|field| i32::from(field.position.1));
+        let (min_x, max_x) = (xs.clone().min().unwrap_or(0), xs.max().unwrap_or(0));
+        let (min_y, max_y) = (ys.clone().min().unwrap_or(0), ys.max().unwrap_or(0));
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+             width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">\n",
+            width,
+            height,
+            min_x - MARGIN,
+            min_y - MARGIN,
+            max_x - min_x + 2 * MARGIN,
+            max_y - min_y + 2 * MARGIN
+        );
+        svg.push_str(&format!("  <title>{}</title>\n", xml_escape(&self.name)));
+        svg.push_str("  <g stroke=\"gray\" stroke-width=\"2\">\n");
+        for &(a, b) in &self.relations {
+            if let (Some(from), Some(to)) = (self.field(a), self.field(b)) {
+                svg.push_str(&format!(
+                    "    <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>\n",
+                    from.position.0, from.position.1, to.position.0, to.position.1
+                ));
+            }
+        }
+        svg.push_str("  </g>\n");
+        svg.push_str(
+            "  <g stroke=\"black\" font-family=\"sans-serif\" \
+             font-size=\"12\" text-anchor=\"middle\">\n",
+        );
+        for field in &self.fields {
+            let (x, y) = field.position;
+            svg.push_str(&format!(
+                "    <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+                x,
+                y,
+                RADIUS,
+                self.set_color(field.set_id)
+            ));
+            svg.push_str(&format!(
+                "    <text x=\"{}\" y=\"{}\" stroke=\"none\">{}</text>\n",
+                x,
+                i32::from(y) + 2 * RADIUS,
+                xml_escape(&field.name)
+            ));
+        }
+        svg.push_str("  </g>\n</svg>\n");
+        svg
+    }
+}
+
 impl crate::validator::Validatable for Board {
     fn validate(&self) -> Result<(), String> {
+        self.check().map_err(|(message, _)| message)
+    }
+
+    fn validate_located(&self) -> Result<(), (String, Option<Location>)> {
+        self.check()
+    }
+}
+
+impl Board {
+    /// Runs all validation checks, reporting the first problem together with
+    /// the path of the offending value (e.g. `fields[7].set_id`) where there is one.
+    fn check(&self) -> Result<(), (String, Option<Location>)> {
+        let at = |path: String| Some(Location::FieldPath(path));
         if self.id.trim().is_empty() {
-            return Err("Board: 'id' must not be empty.".into());
+            return Err(("Board: 'id' must not be empty.".into(), at("id".into())));
         }
         if self.fields.is_empty() {
-            return Err("Board: 'fields' must not be empty.".into());
+            return Err((
+                "Board: 'fields' must not be empty.".into(),
+                at("fields".into()),
+            ));
         }
         // Check for duplicate IDs
         let mut ids = std::collections::HashSet::new();
-        for field in &self.fields {
+        for (i, field) in self.fields.iter().enumerate() {
             if !ids.insert(field.id) {
-                return Err(format!("Board: duplicate field id {}", field.id));
+                return Err((
+                    format!("Board: duplicate field id {}", field.id),
+                    at(format!("fields[{}].id", i)),
+                ));
+            }
+        }
+        // Check that every field belongs to a defined set
+        let set_ids: std::collections::HashSet<u8> = self.sets.iter().map(|set| set.id).collect();
+        for (i, field) in self.fields.iter().enumerate() {
+            if !set_ids.contains(&field.set_id) {
+                return Err((
+                    format!(
+                        "Board: field {} references unknown set id {}",
+                        field.id, field.set_id
+                    ),
+                    at(format!("fields[{}].set_id", i)),
+                ));
+            }
+        }
+        // Check that no relation connects a field to itself
+        for (i, &(a, b)) in self.relations.iter().enumerate() {
+            if a == b {
+                return Err((
+                    format!(
+                        "Board: relation ({},{}) connects field {} to itself",
+                        a, b, a
+                    ),
+                    at(format!("relations[{}]", i)),
+                ));
             }
         }
         // Check that relations reference valid field ids
-        for &(a, b) in &self.relations {
+        for (i, &(a, b)) in self.relations.iter().enumerate() {
             if !ids.contains(&a) || !ids.contains(&b) {
-                return Err(format!(
-                    "Board: relation ({},{}) refers to unknown field id",
-                    a, b
+                return Err((
+                    format!("Board: relation ({},{}) refers to unknown field id", a, b),
+                    at(format!("relations[{}]", i)),
+                ));
+            }
+        }
+        // Check that every border is listed only once, in either order
+        let mut borders = std::collections::HashSet::new();
+        for (i, &(a, b)) in self.relations.iter().enumerate() {
+            if !borders.insert((a.min(b), a.max(b))) {
+                return Err((
+                    format!(
+                        "Board: duplicate relation between fields {} and {}",
+                        a.min(b),
+                        a.max(b)
+                    ),
+                    at(format!("relations[{}]", i)),
+                ));
+            }
+        }
+        // Check that every set contains at least one field
+        for (i, set) in self.sets.iter().enumerate() {
+            if !self.fields.iter().any(|field| field.set_id == set.id) {
+                return Err((
+                    format!("Board: set {} ({}) has no fields", set.id, set.name),
+                    at(format!("sets[{}]", i)),
+                ));
+            }
+        }
+        // Check that every field has at least one border (keep this last)
+        let bordered: std::collections::HashSet<u8> =
+            self.relations.iter().flat_map(|&(a, b)| [a, b]).collect();
+        for (i, field) in self.fields.iter().enumerate() {
+            if !bordered.contains(&field.id) {
+                return Err((
+                    format!("Board: field {} ({}) has no borders", field.id, field.name),
+                    at(format!("fields[{}]", i)),
                 ));
             }
         }
         Ok(())
     }
 }
+
+/// Fluent builder for boards created in code, e.g. for fixtures or map
+/// generators. `build()` runs the same validation as loading from a file.
+#[derive(Debug, Default)]
+pub struct BoardBuilder {
+    board: Board,
+}
+
+impl BoardBuilder {
+    /// Starts an empty board.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the board id (required).
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.board.id = id.into();
+        self
+    }
+
+    /// Sets the board name (required).
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.board.name = name.into();
+        self
+    }
+
+    /// Sets the author.
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.board.author = author.into();
+        self
+    }
+
+    /// Sets the version string.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.board.version = version.into();
+        self
+    }
+
+    /// Sets the description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.board.description = description.into();
+        self
+    }
+
+    /// Adds a set (continent).
+    pub fn add_set(mut self, id: u8, name: impl Into<String>) -> Self {
+        self.board.sets.push(FieldSet {
+            id,
+            name: name.into(),
+        });
+        self
+    }
+
+    /// Adds a field without artwork.
+    pub fn add_field(
+        mut self,
+        id: u8,
+        name: impl Into<String>,
+        set_id: u8,
+        position: (i16, i16),
+        piece_pos: (i16, i16),
+    ) -> Self {
+        self.board.fields.push(FieldElement {
+            id,
+            name: name.into(),
+            set_id,
+            position,
+            piece_pos,
+            filename: String::new(),
+        });
+        self
+    }
+
+    /// Adds a border between two fields.
+    pub fn add_border(mut self, a: u8, b: u8) -> Self {
+        self.board.relations.push((a, b));
+        self
+    }
+
+    /// Finishes the board and runs the usual validation.
+    pub fn build(self) -> Result<Board, String> {
+        if self.board.name.trim().is_empty() {
+            return Err("Board: 'name' must not be empty.".into());
+        }
+        crate::validator::Validatable::validate(&self.board)?;
+        Ok(self.board)
+    }
+}
+
+/// Escapes a string for use inside a quoted DOT identifier.
+fn dot_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escapes a string for use in SVG text and attribute values.
+#[cfg(feature = "svg")]
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two sets of two fields each, with borders forming a ring 1-2-3-4-1.
+    fn small_board() -> BoardBuilder {
+        BoardBuilder::new()
+            .id("test")
+            .name("Test")
+            .add_set(1, "North")
+            .add_set(2, "South")
+            .add_field(1, "A", 1, (0, 0), (0, 0))
+            .add_field(2, "B", 1, (10, 0), (10, 0))
+            .add_field(3, "C", 2, (10, 10), (10, 10))
+            .add_field(4, "D", 2, (0, 10), (0, 10))
+            .add_border(1, 2)
+            .add_border(2, 3)
+            .add_border(3, 4)
+            .add_border(4, 1)
+    }
+
+    #[test]
+    fn small_board_is_valid() {
+        assert!(small_board().build().is_ok());
+    }
+
+    #[test]
+    fn rejects_self_loop() {
+        let err = small_board().add_border(3, 3).build().unwrap_err();
+        assert_eq!(err, "Board: relation (3,3) connects field 3 to itself");
+    }
+
+    #[test]
+    fn rejects_duplicate_relation() {
+        let err = small_board().add_border(1, 2).build().unwrap_err();
+        assert_eq!(err, "Board: duplicate relation between fields 1 and 2");
+    }
+
+    #[test]
+    fn rejects_reversed_duplicate_relation() {
+        let err = small_board().add_border(2, 1).build().unwrap_err();
+        assert_eq!(err, "Board: duplicate relation between fields 1 and 2");
+    }
+
+    #[test]
+    fn neighbors_include_both_directions() {
+        let board = small_board().build().unwrap();
+        assert_eq!(board.neighbors(1), vec![2, 4]);
+        assert_eq!(board.neighbors(3), vec![2, 4]);
+    }
+
+    #[test]
+    fn bounds_include_both_corners() {
+        // small_board spans exactly (0,0) to (10,10)
+        let board = small_board().build().unwrap();
+        assert!(board.validate_bounds(10, 10).is_ok());
+    }
+
+    #[test]
+    fn bounds_reject_one_past_upper_edges() {
+        let board = small_board().build().unwrap();
+        assert!(board.validate_bounds(9, 10).is_err());
+        assert!(board.validate_bounds(10, 9).is_err());
+    }
+
+    #[test]
+    fn bounds_reject_one_past_lower_edges() {
+        for position in [(-1, 5), (5, -1)] {
+            let board = small_board()
+                .add_field(5, "E", 1, position, position)
+                .add_border(5, 1)
+                .build()
+                .unwrap();
+            assert_eq!(
+                board.validate_bounds(10, 10).unwrap_err(),
+                format!(
+                    "Board: field 5 (E) at ({},{}) lies outside 10x10",
+                    position.0, position.1
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn positions_reject_overlapping_fields() {
+        let board = small_board().build().unwrap();
+        // Closest pair is 10 apart on one axis
+        assert!(board.validate_positions(9).is_ok());
+        assert_eq!(
+            board.validate_positions(10).unwrap_err(),
+            "Board: fields 1 and 2 overlap at (0,0)"
+        );
+    }
+
+    #[test]
+    fn positions_reject_identical_positions() {
+        let board = small_board()
+            .add_field(5, "E", 2, (10, 10), (10, 10))
+            .add_border(5, 3)
+            .build()
+            .unwrap();
+        assert_eq!(
+            board.validate_positions(0).unwrap_err(),
+            "Board: fields 3 and 5 overlap at (10,10)"
+        );
+    }
+
+    #[test]
+    fn rejects_set_without_fields() {
+        let err = small_board().add_set(3, "Empty").build().unwrap_err();
+        assert_eq!(err, "Board: set 3 (Empty) has no fields");
+    }
+
+    #[test]
+    fn rejects_field_with_unknown_set() {
+        let err = small_board()
+            .add_field(5, "E", 9, (0, 0), (0, 0))
+            .add_border(5, 1)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, "Board: field 5 references unknown set id 9");
+    }
+
+    #[test]
+    fn ring_has_no_articulation_points() {
+        assert!(small_board()
+            .build()
+            .unwrap()
+            .articulation_points()
+            .is_empty());
+    }
+
+    #[test]
+    fn bridge_endpoints_are_articulation_points() {
+        // Two triangles 1-2-3 and 4-5-6 joined by the bridge 3-4
+        let mut builder = BoardBuilder::new()
+            .id("bridge")
+            .name("Bridge")
+            .add_set(1, "All");
+        for id in 1..=6 {
+            builder = builder.add_field(id, format!("F{}", id), 1, (0, 0), (0, 0));
+        }
+        let board = builder
+            .add_border(1, 2)
+            .add_border(2, 3)
+            .add_border(3, 1)
+            .add_border(4, 5)
+            .add_border(5, 6)
+            .add_border(6, 4)
+            .add_border(3, 4)
+            .build()
+            .unwrap();
+        assert_eq!(board.articulation_points(), HashSet::from([3, 4]));
+    }
+
+    #[test]
+    fn single_connector_is_articulation_point() {
+        // Two triangles 1-2-3 and 5-6-7 joined only through field 4
+        let mut builder = BoardBuilder::new()
+            .id("connector")
+            .name("Connector")
+            .add_set(1, "All");
+        for id in 1..=7 {
+            builder = builder.add_field(id, format!("F{}", id), 1, (0, 0), (0, 0));
+        }
+        let board = builder
+            .add_border(1, 2)
+            .add_border(2, 3)
+            .add_border(3, 1)
+            .add_border(5, 6)
+            .add_border(6, 7)
+            .add_border(7, 5)
+            .add_border(3, 4)
+            .add_border(4, 5)
+            .build()
+            .unwrap();
+        let points = board.articulation_points();
+        assert!(points.contains(&4));
+        assert_eq!(points, HashSet::from([3, 4, 5]));
+    }
+
+    #[test]
+    fn validation_error_carries_field_path() {
+        let builder = small_board()
+            .add_field(5, "E", 9, (0, 0), (0, 0))
+            .add_border(5, 1);
+        let path = std::env::temp_dir().join("teg-rw-field-path.board.ron");
+        crate::loader::save_ron(&builder.board, &path).unwrap();
+        let err = crate::loader::load_and_validate_ron::<Board>(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            err.location(),
+            Some(Location::FieldPath("fields[4].set_id".into()))
+        );
+    }
+
+    #[cfg(feature = "json")]
+    mod json {
+        use super::*;
+        use crate::error::DataError;
+
+        #[test]
+        fn board_round_trips_through_json() {
+            let board = small_board().build().unwrap();
+            assert_eq!(Board::from_json(&board.to_json().unwrap()).unwrap(), board);
+            assert_eq!(
+                Board::from_json(&board.to_json_pretty().unwrap()).unwrap(),
+                board
+            );
+        }
+
+        #[test]
+        fn invalid_board_json_is_rejected() {
+            let mut builder = small_board().add_border(1, 1);
+            builder.board.id = "self-loop".into();
+            let json = builder.board.to_json().unwrap();
+            let err = Board::from_json(&json).unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("Board: relation (1,1) connects field 1 to itself"));
+        }
+
+        #[test]
+        fn malformed_json_file_reports_position() {
+            let path = std::env::temp_dir().join("teg-rw-malformed.board.json");
+            std::fs::write(&path, "{\n  \"id\": }").unwrap();
+            let err = Board::from_json_file(&path).unwrap_err();
+            std::fs::remove_file(&path).unwrap();
+            assert!(matches!(err, DataError::ParseJson { .. }));
+            assert_eq!(err.location(), Some(Location::Position { line: 2, col: 9 }));
+        }
+
+        #[test]
+        fn json_file_validation_reports_field_path() {
+            let builder = small_board()
+                .add_field(5, "E", 9, (0, 0), (0, 0))
+                .add_border(5, 1);
+            let path = std::env::temp_dir().join("teg-rw-field-path.board.json");
+            std::fs::write(&path, builder.board.to_json().unwrap()).unwrap();
+            let err = Board::from_json_file(&path).unwrap_err();
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(
+                err.location(),
+                Some(Location::FieldPath("fields[4].set_id".into()))
+            );
+        }
+    }
+}
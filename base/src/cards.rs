@@ -0,0 +1,297 @@
+use crate::board::Board;
+use crate::dices::RandomSource;
+use crate::rule::RuleParameters;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Top-level structure for card definitions (`<name>.cards.ron`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct CardSet {
+    /// Unique identifier for this card definition.
+    pub id: String,
+
+    /// Human-readable name of the card set.
+    pub name: String,
+
+    /// Author of the card set.
+    pub author: String,
+
+    /// Version string (semantic or otherwise).
+    pub version: String,
+
+    /// Short description of the card set.
+    pub description: String,
+
+    /// All cards in the deck.
+    pub cards: Vec<Card>,
+}
+
+/// A single territory card.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Card {
+    /// Board field shown on the card (`None` for wildcards).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field_id: Option<u8>,
+
+    /// Symbol used when trading cards in.
+    pub symbol: CardSymbol,
+}
+
+/// Symbol printed on a card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CardSymbol {
+    Infantry,
+    Cavalry,
+    Artillery,
+    /// Stands in for any other symbol.
+    Wildcard,
+}
+
+impl CardSet {
+    /// Loads and validates a card definition from a RON file.
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> crate::error::Result<Self> {
+        crate::loader::load_and_validate_ron(path)
+    }
+
+    /// Builds a shuffled deck with one card per board field plus `wildcards`.
+    ///
+    /// Fields are taken in id order and get Infantry, Cavalry and Artillery
+    /// in turn. The same board, wildcard count and rolls always give the same deck.
+    pub fn build_deck(board: &Board, wildcards: usize, rng: &mut dyn RandomSource) -> Vec<Card> {
+        const SYMBOLS: [CardSymbol; 3] = [
+            CardSymbol::Infantry,
+            CardSymbol::Cavalry,
+            CardSymbol::Artillery,
+        ];
+        let mut field_ids: Vec<u8> = board.fields.iter().map(|field| field.id).collect();
+        field_ids.sort_unstable();
+
+        let mut deck: Vec<Card> = field_ids
+            .into_iter()
+            .zip(SYMBOLS.iter().cycle())
+            .map(|(field_id, &symbol)| Card {
+                field_id: Some(field_id),
+                symbol,
+            })
+            .collect();
+        deck.extend((0..wildcards).map(|_| Card {
+            field_id: None,
+            symbol: CardSymbol::Wildcard,
+        }));
+        shuffle(&mut deck, rng);
+        deck
+    }
+}
+
+/// A deck in play: cards are drawn from the top and traded cards go to the
+/// discard pile, which is reshuffled into a new draw pile once it runs out.
+#[derive(Debug, Clone)]
+pub struct Deck {
+    draw_pile: Vec<Card>,
+    discard_pile: Vec<Card>,
+}
+
+impl Deck {
+    /// Creates a deck from already shuffled cards.
+    pub fn new(cards: Vec<Card>) -> Self {
+        Self {
+            draw_pile: cards,
+            discard_pile: Vec::new(),
+        }
+    }
+
+    /// Draws the top card, reshuffling the discard pile with `rng` if the
+    /// draw pile is empty. Returns `None` once both piles are empty.
+    pub fn draw(&mut self, rng: &mut dyn RandomSource) -> Option<Card> {
+        if self.draw_pile.is_empty() {
+            std::mem::swap(&mut self.draw_pile, &mut self.discard_pile);
+            shuffle(&mut self.draw_pile, rng);
+        }
+        self.draw_pile.pop()
+    }
+
+    /// Puts a card on the discard pile.
+    pub fn discard(&mut self, card: Card) {
+        self.discard_pile.push(card);
+    }
+
+    /// Number of cards left in the draw pile.
+    pub fn remaining(&self) -> usize {
+        self.draw_pile.len()
+    }
+}
+
+/// Fisher–Yates shuffle driven by the given source of rolls.
+fn shuffle(cards: &mut [Card], rng: &mut dyn RandomSource) {
+    for i in (1..cards.len()).rev() {
+        let j = pick(rng, i + 1);
+        cards.swap(i, j);
+    }
+}
+
+/// Picks an index in `0..bound` (`bound > 0`) from die rolls.
+/// Bounds above 255 combine several rolls into one number and redraw
+/// values that would skew the result.
+fn pick(rng: &mut dyn RandomSource, bound: usize) -> usize {
+    // Reduces a roll into `0..faces`, even if the source strays outside `1..=faces`
+    fn face(rng: &mut dyn RandomSource, faces: u8) -> u128 {
+        let faces = u128::from(faces);
+        (u128::from(rng.next_die(faces as u8)) + faces - 1) % faces
+    }
+    if let Ok(faces) = u8::try_from(bound) {
+        return face(rng, faces) as usize;
+    }
+    let bound = bound as u128;
+    loop {
+        let (mut value, mut span) = (0u128, 1u128);
+        while span < bound {
+            value = value * 255 + face(rng, u8::MAX);
+            span *= 255;
+        }
+        if value < span - span % bound {
+            return (value % bound) as usize;
+        }
+    }
+}
+
+/// Checks whether three cards can be traded in: three of the same symbol,
+/// one of each symbol, or any cards completed by a wildcard.
+pub fn is_valid_trade(cards: &[Card; 3]) -> bool {
+    let symbols: Vec<CardSymbol> = cards
+        .iter()
+        .map(|card| card.symbol)
+        .filter(|&symbol| symbol != CardSymbol::Wildcard)
+        .collect();
+    match symbols.as_slice() {
+        [a, b, c] => (a == b && b == c) || (a != b && b != c && a != c),
+        // At least one wildcard, which completes any pair or single
+        _ => true,
+    }
+}
+
+/// Bonus figures for the trade with the given zero-based index.
+/// Trades beyond the end of `card_bonus_sequence` keep its last value.
+pub fn trade_bonus(trade_index: usize, rules: &RuleParameters) -> u32 {
+    let sequence = &rules.card_bonus_sequence;
+    sequence
+        .get(trade_index)
+        .or_else(|| sequence.last())
+        .map_or(0, |&bonus| u32::from(bonus))
+}
+
+impl crate::validator::Validatable for CardSet {
+    fn validate(&self) -> Result<(), String> {
+        if self.id.trim().is_empty() {
+            return Err("Cards: 'id' must not be empty.".to_string());
+        }
+        if self.cards.is_empty() {
+            return Err("Cards: deck must contain at least one card.".to_string());
+        }
+        // Regular cards show exactly one field, wildcards none
+        let mut fields = HashSet::new();
+        for card in &self.cards {
+            match (card.symbol, card.field_id) {
+                (CardSymbol::Wildcard, Some(field_id)) => {
+                    return Err(format!(
+                        "Cards: wildcard must not reference a field (field {})",
+                        field_id
+                    ));
+                }
+                (CardSymbol::Wildcard, None) => {}
+                (symbol, None) => {
+                    return Err(format!("Cards: {:?} card has no field", symbol));
+                }
+                (_, Some(field_id)) => {
+                    if !fields.insert(field_id) {
+                        return Err(format!(
+                            "Cards: field {} appears on more than one card",
+                            field_id
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CardSymbol::*;
+
+    fn hand(symbols: [CardSymbol; 3]) -> [Card; 3] {
+        symbols.map(|symbol| Card {
+            field_id: None,
+            symbol,
+        })
+    }
+
+    #[test]
+    fn three_of_a_kind_is_valid() {
+        assert!(is_valid_trade(&hand([Cavalry, Cavalry, Cavalry])));
+    }
+
+    #[test]
+    fn one_of_each_is_valid() {
+        assert!(is_valid_trade(&hand([Infantry, Cavalry, Artillery])));
+    }
+
+    #[test]
+    fn wildcard_completes_any_pair() {
+        assert!(is_valid_trade(&hand([Infantry, Infantry, Wildcard])));
+        assert!(is_valid_trade(&hand([Infantry, Artillery, Wildcard])));
+    }
+
+    #[test]
+    fn mixed_set_is_invalid() {
+        assert!(!is_valid_trade(&hand([Infantry, Infantry, Artillery])));
+    }
+
+    fn two_field_board() -> Board {
+        crate::board::BoardBuilder::new()
+            .id("test")
+            .name("Test")
+            .add_set(1, "Only")
+            .add_field(1, "A", 1, (0, 0), (0, 0))
+            .add_field(2, "B", 1, (10, 0), (10, 0))
+            .add_border(1, 2)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn deck_follows_preset_rolls() {
+        // Shuffling three cards takes a roll for index 2, then for index 1;
+        // rolling the highest face each time keeps the order.
+        let mut rng = crate::dices::SequenceSource::new(&[3, 2]);
+        let deck = CardSet::build_deck(&two_field_board(), 1, &mut rng);
+        let fields: Vec<_> = deck.iter().map(|card| card.field_id).collect();
+        assert_eq!(fields, [Some(1), Some(2), None]);
+    }
+
+    #[test]
+    fn deck_reshuffles_discards_with_given_rng() {
+        let mut rng = crate::dices::SequenceSource::new(&[1]);
+        let mut deck = Deck::new(Vec::new());
+        let card = |field_id| Card {
+            field_id: Some(field_id),
+            symbol: Infantry,
+        };
+        deck.discard(card(1));
+        deck.discard(card(2));
+        assert_eq!(deck.draw(&mut rng), Some(card(1)));
+        assert_eq!(deck.draw(&mut rng), Some(card(2)));
+        assert_eq!(deck.draw(&mut rng), None);
+    }
+
+    #[test]
+    fn pick_covers_bounds_above_255() {
+        let mut rng = crate::dices::DiceRoller::new(3);
+        for _ in 0..1000 {
+            assert!(pick(&mut rng, 1000) < 1000);
+        }
+    }
+}
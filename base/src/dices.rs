@@ -1,22 +1,491 @@
-#[derive(Deserialize)]
-struct DicePiece {
-    value: u8,
-    image: String,
-}
-
-#[derive(Deserialize)]
-struct DiceVariant {
-    id: u8,
-    name: String,
-    pieces: Vec<DicePiece>,
-}
-
-#[derive(Deserialize)]
-struct DiceSetCollection {
-    id: String,
-    name: String,
-    author: String,
-    version: String,
-    description: String,
-    dice_sets: Vec<DiceVariant>,
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// A single face of a die.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct DicePiece {
+    /// Value shown by this face.
+    pub value: u8,
+
+    /// Image used to render this face.
+    pub image: String,
+}
+
+/// One kind of die (e.g. "White", "Red"), made up of its faces.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct DiceVariant {
+    /// Numeric ID (must be unique).
+    pub id: u8,
+
+    /// Name of the variant.
+    pub name: String,
+
+    /// Faces of the die.
+    pub pieces: Vec<DicePiece>,
+
+    /// Allows repeated or non-contiguous face values (e.g. `[2, 2, 3, 5]`).
+    /// Off by default, so ordinary dice are checked for a plain range.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub custom_faces: bool,
+}
+
+/// Top-level structure for dice definitions (`<name>.dices.ron`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct DiceSetCollection {
+    /// Unique identifier for this dice definition.
+    pub id: String,
+
+    /// Human-readable name of the dice set.
+    pub name: String,
+
+    /// Author of the dice set.
+    pub author: String,
+
+    /// Version string (semantic or otherwise).
+    pub version: String,
+
+    /// Short description of the dice set.
+    pub description: String,
+
+    /// Available dice variants.
+    pub dice_sets: Vec<DiceVariant>,
+}
+
+impl DiceVariant {
+    /// Rolls this die by picking one of its faces uniformly and returning its value.
+    /// Unlike [`DiceRoller::roll`], this follows the faces actually defined,
+    /// so repeated or non-standard values keep their relative frequency.
+    ///
+    /// Returns `None` if the variant has no faces. Rolls outside `1..=faces`
+    /// are wrapped around into range. Only the first 255 faces can come up;
+    /// validation rejects longer dice.
+    pub fn roll(&self, rng: &mut dyn RandomSource) -> Option<u8> {
+        let faces = self.pieces.len().min(usize::from(u8::MAX));
+        if faces == 0 {
+            return None;
+        }
+        let roll = usize::from(rng.next_die(faces as u8));
+        let index = (roll + faces - 1) % faces;
+        Some(self.pieces[index].value)
+    }
+}
+
+impl DiceSetCollection {
+    /// Loads and validates a dice definition from a RON file.
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> crate::error::Result<Self> {
+        crate::loader::load_and_validate_ron(path)
+    }
+
+    /// Writes the dice definition to a RON file in pretty format.
+    pub fn to_file(&self, path: impl AsRef<std::path::Path>) -> crate::error::Result<()> {
+        crate::loader::save_ron(self, path)
+    }
+}
+
+impl DiceSetCollection {
+    /// Checks the dice definition and reports the first problem as a typed error.
+    ///
+    /// Every variant needs between 1 and 255 faces and an image per face.
+    /// Unless it sets `custom_faces`, its values must also be unique and
+    /// without gaps, starting at 0 or 1 (e.g. `1..=6` or `0..=2`).
+    /// A 0-based range is allowed on purpose, although standard dice count
+    /// from 1: the shipped `ssp.dices.ron` (rock, scissors, paper) numbers its faces `0..=2`.
+    pub fn validate(&self) -> Result<(), DiceValidationError> {
+        if self.id.trim().is_empty() {
+            return Err(DiceValidationError::EmptyId);
+        }
+        for variant in &self.dice_sets {
+            if variant.pieces.is_empty() {
+                return Err(DiceValidationError::NoPieces(variant.id));
+            }
+            if variant.pieces.len() > usize::from(u8::MAX) {
+                return Err(DiceValidationError::TooManyPieces(variant.id));
+            }
+            let mut values = Vec::with_capacity(variant.pieces.len());
+            for piece in &variant.pieces {
+                if !variant.custom_faces && values.contains(&piece.value) {
+                    return Err(DiceValidationError::DuplicateValue {
+                        variant: variant.id,
+                        value: piece.value,
+                    });
+                }
+                if piece.image.trim().is_empty() {
+                    return Err(DiceValidationError::EmptyImage {
+                        variant: variant.id,
+                        value: piece.value,
+                    });
+                }
+                values.push(piece.value);
+            }
+            if variant.custom_faces {
+                continue;
+            }
+            values.sort_unstable();
+            let first = values[0];
+            let gap_free = values
+                .iter()
+                .zip(first..)
+                .all(|(&value, expected)| value == expected);
+            if first > 1 || !gap_free {
+                return Err(DiceValidationError::NonContiguous(variant.id));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl crate::validator::Validatable for DiceSetCollection {
+    fn validate(&self) -> Result<(), String> {
+        DiceSetCollection::validate(self).map_err(|e| e.to_string())
+    }
+}
+
+/// Problems found while validating a [`DiceSetCollection`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DiceValidationError {
+    #[error("Dices: 'id' must not be empty.")]
+    EmptyId,
+
+    #[error("Dices: variant {0} has no pieces")]
+    NoPieces(u8),
+
+    #[error("Dices: variant {0} has more than 255 pieces")]
+    TooManyPieces(u8),
+
+    #[error("Dices: variant {variant} has duplicate value {value}")]
+    DuplicateValue { variant: u8, value: u8 },
+
+    #[error("Dices: values of variant {0} do not form a contiguous range")]
+    NonContiguous(u8),
+
+    #[error("Dices: variant {variant} has no image for value {value}")]
+    EmptyImage { variant: u8, value: u8 },
+}
+
+/// Source of die rolls, so code that rolls dice can be driven by a seeded
+/// [`DiceRoller`] in play and by a fixed [`SequenceSource`] in tests.
+pub trait RandomSource {
+    /// Rolls a die with the given number of faces, returning `1..=faces`.
+    fn next_die(&mut self, faces: u8) -> u8;
+}
+
+impl RandomSource for DiceRoller {
+    fn next_die(&mut self, faces: u8) -> u8 {
+        self.roll(faces)
+    }
+}
+
+/// Replays a preset list of rolls in order.
+///
+/// Rolls outside `1..=faces` are wrapped around into range, so `0` on a
+/// six-sided die reads as 6 and `7` as 1.
+#[derive(Debug, Clone)]
+pub struct SequenceSource {
+    rolls: Vec<u8>,
+    next: usize,
+}
+
+impl SequenceSource {
+    /// Creates a source that returns `rolls` one after another.
+    pub fn new(rolls: &[u8]) -> Self {
+        Self {
+            rolls: rolls.to_vec(),
+            next: 0,
+        }
+    }
+}
+
+impl RandomSource for SequenceSource {
+    /// # Panics
+    /// Panics if `faces` is zero or once all preset rolls are used up.
+    fn next_die(&mut self, faces: u8) -> u8 {
+        assert!(faces > 0, "a die needs at least one face");
+        let roll = *self
+            .rolls
+            .get(self.next)
+            .expect("SequenceSource ran out of rolls");
+        self.next += 1;
+        ((u16::from(roll) + u16::from(faces) - 1) % u16::from(faces) + 1) as u8
+    }
+}
+
+/// Deterministic dice roller.
+///
+/// Wraps a SplitMix64 generator, so the same seed yields the same rolls
+/// on every run and platform. Not suitable for anything security related.
+#[derive(Debug, Clone)]
+pub struct DiceRoller {
+    state: u64,
+}
+
+impl DiceRoller {
+    /// Creates a roller from the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Rolls a single die with the given number of faces, returning `1..=faces`.
+    ///
+    /// # Panics
+    /// Panics if `faces` is zero.
+    pub fn roll(&mut self, faces: u8) -> u8 {
+        assert!(faces > 0, "a die needs at least one face");
+        self.below(u64::from(faces)) as u8 + 1
+    }
+
+    /// Rolls `n` dice with the given number of faces.
+    pub fn roll_many(&mut self, n: usize, faces: u8) -> Vec<u8> {
+        (0..n).map(|_| self.roll(faces)).collect()
+    }
+
+    /// Returns a uniformly distributed value in `0..bound` (rejection sampling, no modulo bias).
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Resolves one encounter between rolled dice, highest against highest.
+///
+/// Both sides are sorted in descending order and compared pairwise for as
+/// many pairs as the smaller side has dice; ties go to the defender.
+/// Accepts 1–3 attacker dice and 1–2 defender dice.
+/// Returns `(attacker_losses, defender_losses)`.
+pub fn resolve_attack(attacker_dice: &[u8], defender_dice: &[u8]) -> Result<(u32, u32), String> {
+    if !(1..=3).contains(&attacker_dice.len()) {
+        return Err(format!(
+            "Combat: attacker needs 1 to 3 dice, got {}",
+            attacker_dice.len()
+        ));
+    }
+    if !(1..=2).contains(&defender_dice.len()) {
+        return Err(format!(
+            "Combat: defender needs 1 to 2 dice, got {}",
+            defender_dice.len()
+        ));
+    }
+    let mut attacker = attacker_dice.to_vec();
+    let mut defender = defender_dice.to_vec();
+    attacker.sort_unstable_by(|a, b| b.cmp(a));
+    defender.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut losses = (0, 0);
+    for (a, d) in attacker.iter().zip(&defender) {
+        if a > d {
+            losses.1 += 1;
+        } else {
+            losses.0 += 1;
+        }
+    }
+    Ok(losses)
+}
+
+/// Rolls six-sided dice for both sides and resolves the encounter with
+/// [`resolve_attack`]; the attacker's dice are rolled first.
+pub fn roll_attack(
+    attacker_count: u8,
+    defender_count: u8,
+    rng: &mut dyn RandomSource,
+) -> Result<(u32, u32), String> {
+    let attacker: Vec<u8> = (0..attacker_count).map(|_| rng.next_die(6)).collect();
+    let defender: Vec<u8> = (0..defender_count).map(|_| rng.next_die(6)).collect();
+    resolve_attack(&attacker, &defender)
+}
+
+/// Outcome distribution of a single encounter, as exact counts over all rolls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombatOdds {
+    /// Number of equally likely rolls that were enumerated.
+    pub total: u32,
+
+    /// Number of rolls per `(attacker_losses, defender_losses)` outcome.
+    pub outcomes: BTreeMap<(u32, u32), u32>,
+}
+
+impl CombatOdds {
+    /// Probability of the given `(attacker_losses, defender_losses)` outcome.
+    pub fn probability(&self, outcome: (u32, u32)) -> f64 {
+        let count = self.outcomes.get(&outcome).copied().unwrap_or(0);
+        f64::from(count) / f64::from(self.total)
+    }
+}
+
+/// Enumerates every roll of six-sided dice for one encounter and tallies
+/// the outcomes of [`resolve_attack`]. Accepts the same dice counts.
+pub fn attack_odds(attacker_count: u8, defender_count: u8) -> Result<CombatOdds, String> {
+    const FACES: u8 = 6;
+    let dice = usize::from(attacker_count) + usize::from(defender_count);
+    let mut odds = CombatOdds {
+        total: 0,
+        outcomes: BTreeMap::new(),
+    };
+    let mut roll = vec![1; dice];
+    loop {
+        let (attacker, defender) = roll.split_at(usize::from(attacker_count));
+        let outcome = resolve_attack(attacker, defender)?;
+        *odds.outcomes.entry(outcome).or_insert(0) += 1;
+        odds.total += 1;
+
+        // Advance to the next combination, like an odometer
+        let Some(position) = roll.iter().position(|&face| face < FACES) else {
+            break;
+        };
+        roll[position] += 1;
+        roll[..position].fill(1);
+    }
+    Ok(odds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(id: u8, values: &[u8]) -> DiceVariant {
+        DiceVariant {
+            id,
+            name: format!("Variant {}", id),
+            pieces: values
+                .iter()
+                .map(|&value| DicePiece {
+                    value,
+                    image: format!("face_{}.png", value),
+                })
+                .collect(),
+            custom_faces: false,
+        }
+    }
+
+    fn collection(variants: Vec<DiceVariant>) -> DiceSetCollection {
+        DiceSetCollection {
+            id: "test".into(),
+            name: "Test".into(),
+            author: String::new(),
+            version: String::new(),
+            description: String::new(),
+            dice_sets: variants,
+        }
+    }
+
+    #[test]
+    fn accepts_standard_and_zero_based_dice() {
+        let dice = collection(vec![
+            variant(1, &[1, 2, 3, 4, 5, 6]),
+            variant(2, &[0, 1, 2]),
+        ]);
+        assert_eq!(dice.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_six_sided_die_with_gap() {
+        let dice = collection(vec![variant(1, &[1, 2, 3, 4, 5, 7])]);
+        assert_eq!(dice.validate(), Err(DiceValidationError::NonContiguous(1)));
+    }
+
+    #[test]
+    fn repeated_faces_need_custom_faces() {
+        let mut die = variant(1, &[2, 2, 3, 5]);
+        assert_eq!(
+            collection(vec![die.clone()]).validate(),
+            Err(DiceValidationError::DuplicateValue {
+                variant: 1,
+                value: 2
+            })
+        );
+        die.custom_faces = true;
+        assert_eq!(collection(vec![die]).validate(), Ok(()));
+    }
+
+    #[test]
+    fn custom_faces_are_read_from_ron() {
+        let die: DiceVariant = ron::from_str(
+            r#"(id: 1, name: "Odd", custom_faces: true,
+                pieces: [(value: 2, image: "a.png"), (value: 2, image: "b.png")])"#,
+        )
+        .unwrap();
+        assert!(die.custom_faces);
+        assert_eq!(collection(vec![die]).validate(), Ok(()));
+    }
+
+    #[test]
+    fn roll_picks_defined_faces() {
+        let die = variant(1, &[2, 2, 3, 5]);
+        let mut rng = SequenceSource::new(&[1, 2, 3, 4]);
+        let rolls: Vec<_> = (0..4).map(|_| die.roll(&mut rng)).collect();
+        assert_eq!(rolls, [Some(2), Some(2), Some(3), Some(5)]);
+    }
+
+    #[test]
+    fn roll_wraps_out_of_range_values() {
+        let die = variant(1, &[1, 2, 3, 4, 5, 6]);
+        let mut rng = SequenceSource::new(&[0, 7]);
+        assert_eq!(die.roll(&mut rng), Some(6));
+        assert_eq!(die.roll(&mut rng), Some(1));
+    }
+
+    #[test]
+    fn sequence_source_stays_within_faces() {
+        let mut rng = SequenceSource::new(&[0, 3, 6, 7, 255]);
+        let rolls: Vec<u8> = (0..5).map(|_| rng.next_die(6)).collect();
+        assert_eq!(rolls, [6, 3, 6, 1, 3]);
+    }
+
+    #[test]
+    fn roll_handles_empty_and_oversized_dice() {
+        let mut rng = DiceRoller::new(7);
+        assert_eq!(variant(1, &[]).roll(&mut rng), None);
+        let values: Vec<u8> = (0..=255).collect();
+        let die = variant(2, &values);
+        assert_eq!(
+            collection(vec![die.clone()]).validate(),
+            Err(DiceValidationError::TooManyPieces(2))
+        );
+        for _ in 0..100 {
+            assert!(die.roll(&mut rng).is_some());
+        }
+    }
+
+    #[test]
+    fn teg_dices_survive_save_and_reload() {
+        let shipped =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../wad/game/teg.dices.ron");
+        let dice = DiceSetCollection::from_file(shipped).unwrap();
+        let path = std::env::temp_dir().join("teg-rw-round-trip.dices.ron");
+        dice.to_file(&path).unwrap();
+        let reloaded = DiceSetCollection::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reloaded, dice);
+    }
+
+    #[test]
+    fn three_against_two_odds() {
+        let odds = attack_odds(3, 2).unwrap();
+        assert_eq!(odds.total, 7776);
+        assert_eq!(
+            odds.outcomes,
+            BTreeMap::from([((0, 2), 2890), ((1, 1), 2611), ((2, 0), 2275)])
+        );
+        let sum: f64 = odds
+            .outcomes
+            .keys()
+            .map(|&outcome| odds.probability(outcome))
+            .sum();
+        assert!((sum - 1.0).abs() < 1e-12);
+    }
 }
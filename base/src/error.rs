@@ -1,31 +1,78 @@
-use ron::de::Error as RonError;
+use ron::error::SpannedError as RonError;
 use std::io;
 use std::path::PathBuf;
 use thiserror::Error;
 
+/// Result type used by all loaders of this crate.
 pub type Result<T> = std::result::Result<T, DataError>;
 
+/// Canonical error for loading and saving data files.
 #[derive(Debug, Error)]
 pub enum DataError {
-    #[error("I/O error while reading {path:?}: {source}")]
+    #[error("I/O error on {path:?}: {source}")]
     Io {
         path: PathBuf,
         #[source]
         source: io::Error,
     },
 
-    #[error("RON parse error in {path:?}: {source}")]
+    /// Displayed as `path:line:col: message`, like compiler diagnostics.
+    #[error(
+        "{}:{}:{}: {}",
+        path.display(),
+        source.position.line,
+        source.position.col,
+        source.code
+    )]
     ParseRon {
         path: PathBuf,
         #[source]
         source: RonError,
     },
 
+    #[error("RON serialize error for {path:?}: {source}")]
+    SerializeRon {
+        path: PathBuf,
+        #[source]
+        source: ron::Error,
+    },
+
+    #[cfg(feature = "json")]
+    #[error("{}: {}", path.display(), source)]
+    ParseJson {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
     #[error("Asset not found: {0}")]
     AssetNotFound(PathBuf),
 
     #[error("Validation error: {0}")]
     Validation(String),
+
+    /// A validation error that knows where in the file the problem is.
+    #[error("Validation error at {location}: {message}")]
+    ValidationAt { message: String, location: Location },
+}
+
+/// Where in a data file a problem was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Location {
+    /// Line and column (both 1-based).
+    Position { line: usize, col: usize },
+
+    /// Path to the offending value, e.g. `fields[7].set_id`.
+    FieldPath(String),
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::Position { line, col } => write!(f, "{}:{}", line, col),
+            Location::FieldPath(path) => f.write_str(path),
+        }
+    }
 }
 
 // Direkt Zugriff auf die Position im RON-Fehler:
@@ -37,4 +84,23 @@ impl DataError {
             _ => None,
         }
     }
+
+    /// Location of the problem for any kind of error, if one is known.
+    /// Parse errors report their line and column; validation errors the
+    /// location they were raised with.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            DataError::ParseRon { source, .. } => Some(Location::Position {
+                line: source.position.line,
+                col: source.position.col,
+            }),
+            #[cfg(feature = "json")]
+            DataError::ParseJson { source, .. } => Some(Location::Position {
+                line: source.line(),
+                col: source.column(),
+            }),
+            DataError::ValidationAt { location, .. } => Some(location.clone()),
+            _ => None,
+        }
+    }
 }
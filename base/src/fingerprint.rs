@@ -0,0 +1,28 @@
+//! Fingerprints of loaded game data, for checking that two clients use the same data.
+//!
+use crate::board::Board;
+use crate::rule::RuleDefinition;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Computes a stable 64-bit fingerprint of a board and its rules.
+///
+/// The board is hashed in its [`Board::normalized`] order and the rules
+/// serialize their maps with sorted keys, so equal data always gives the same
+/// value regardless of listing or hash order. The hash is FNV-1a: good enough
+/// to detect accidental mismatches, but not cryptographic and not
+/// resistant to deliberate tampering.
+pub fn data_fingerprint(board: &Board, rules: &RuleDefinition) -> u64 {
+    let board = ron::to_string(&board.normalized()).expect("boards always serialize");
+    let rules = ron::to_string(rules).expect("rule definitions always serialize");
+    let mut hash = FNV_OFFSET_BASIS;
+    // Separate the two parts so moving bytes between them changes the hash
+    for part in [board.as_bytes(), &[0], rules.as_bytes()] {
+        for &byte in part {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    hash
+}
@@ -1,6 +1,15 @@
-use serde::Deserialize;
+use crate::board::Board;
+use crate::cards::CardSet;
+use crate::dices::DiceSetCollection;
+use crate::error::DataError;
+use crate::loader::load_and_validate_ron;
+use crate::pieces::PiecesDefinition;
+use crate::rule::RuleDefinition;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct GameDefinition {
     /// Unique identifier for this game
     pub id: String,
@@ -19,21 +28,128 @@ pub struct GameDefinition {
 
     // Optional component references.
     // Each of these maps to a RON file with a matching suffix,
-    // e.g. "" => loads "<id>.board.ron", "custom" => "custom.board.ron"
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub board: String,
+    // e.g. "" => loads "<id>.board.ron", "custom" => "custom.board.ron",
+    // a missing entry means the game does not use that component.
+    // Names are written as plain strings; `Some("...")` is accepted as well.
+    #[serde(
+        default,
+        deserialize_with = "component_name",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub board: Option<String>,
 
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub cards: String,
+    #[serde(
+        default,
+        deserialize_with = "component_name",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub cards: Option<String>,
 
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub dices: String,
+    #[serde(
+        default,
+        deserialize_with = "component_name",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub dices: Option<String>,
 
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub pieces: String,
+    #[serde(
+        default,
+        deserialize_with = "component_name",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pieces: Option<String>,
 
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub rule: String,
+    #[serde(
+        default,
+        deserialize_with = "component_name",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub rule: Option<String>,
+}
+
+/// Reads a component reference written either as a bare string (`board: ""`)
+/// or as an explicit option (`board: Some("")`); only absent fields are `None`.
+fn component_name<'de, D>(deserializer: D) -> std::result::Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Name {
+        Bare(String),
+        Wrapped(Option<String>),
+    }
+    Ok(match Name::deserialize(deserializer)? {
+        Name::Bare(name) => Some(name),
+        Name::Wrapped(name) => name,
+    })
+}
+
+/// Full paths of the component files referenced by a [`GameDefinition`].
+/// Components the game does not use are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedPaths {
+    pub board: Option<PathBuf>,
+    pub cards: Option<PathBuf>,
+    pub dices: Option<PathBuf>,
+    pub pieces: Option<PathBuf>,
+    pub rule: Option<PathBuf>,
+}
+
+/// All components of a game, loaded and validated.
+#[derive(Debug)]
+pub struct GameData {
+    pub board: Option<Board>,
+    pub cards: Option<CardSet>,
+    pub dices: Option<DiceSetCollection>,
+    pub pieces: Option<PiecesDefinition>,
+    pub rule: Option<RuleDefinition>,
+}
+
+impl GameDefinition {
+    /// Builds the path of every component inside `base_dir`:
+    /// `""` resolves to `<id>.<suffix>.ron`, any other name to `<name>.<suffix>.ron`.
+    pub fn resolve_paths(&self, base_dir: &Path) -> ResolvedPaths {
+        let resolve = |name: Option<&str>, suffix: &str| {
+            name.map(|name| {
+                let prefix = if name.is_empty() { &self.id } else { name };
+                base_dir.join(format!("{}.{}.ron", prefix, suffix))
+            })
+        };
+        ResolvedPaths {
+            board: resolve(self.board.as_deref(), "board"),
+            cards: resolve(self.cards.as_deref(), "cards"),
+            dices: resolve(self.dices.as_deref(), "dices"),
+            pieces: resolve(self.pieces.as_deref(), "pieces"),
+            rule: resolve(self.rule.as_deref(), "rule"),
+        }
+    }
+}
+
+/// Loads and validates every component referenced by `def` from `base_dir`.
+/// When both a board and a rule are present, the rule is also checked
+/// against the board.
+pub fn load_game(def: &GameDefinition, base_dir: &Path) -> crate::error::Result<GameData> {
+    fn load<T>(path: Option<PathBuf>) -> crate::error::Result<Option<T>>
+    where
+        T: serde::de::DeserializeOwned + crate::validator::Validatable,
+    {
+        path.map(load_and_validate_ron).transpose()
+    }
+
+    let paths = def.resolve_paths(base_dir);
+    let data = GameData {
+        board: load(paths.board)?,
+        cards: load(paths.cards)?,
+        dices: load(paths.dices)?,
+        pieces: load(paths.pieces)?,
+        rule: load(paths.rule)?,
+    };
+    if let (Some(board), Some(rule)) = (&data.board, &data.rule) {
+        rule.validate_against_board(board)
+            .map_err(DataError::Validation)?;
+    }
+    Ok(data)
 }
 
 impl crate::validator::Validatable for GameDefinition {
@@ -48,3 +164,45 @@ impl crate::validator::Validatable for GameDefinition {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_bare_and_wrapped_component_names() {
+        let game: GameDefinition = ron::from_str(
+            r#"GameDefinition(
+                id: "teg",
+                name: "TEG Classic",
+                author: "",
+                version: "",
+                description: "",
+                rule: "",
+                board: Some(""),
+                cards: "classic",
+            )"#,
+        )
+        .unwrap();
+        assert_eq!(game.rule.as_deref(), Some(""));
+        assert_eq!(game.board.as_deref(), Some(""));
+        assert_eq!(game.cards.as_deref(), Some("classic"));
+        assert_eq!(game.dices, None);
+
+        let paths = game.resolve_paths(Path::new("wad"));
+        assert_eq!(paths.board, Some(PathBuf::from("wad/teg.board.ron")));
+        assert_eq!(paths.cards, Some(PathBuf::from("wad/classic.cards.ron")));
+        assert_eq!(paths.pieces, None);
+    }
+
+    #[test]
+    fn shipped_game_loads_all_components() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../wad/game");
+        let game: GameDefinition = load_and_validate_ron(dir.join("teg.game.ron")).unwrap();
+        let data = load_game(&game, &dir).unwrap();
+        assert!(data.board.is_some() && data.rule.is_some());
+        assert!(data.dices.is_some() && data.pieces.is_some());
+        // TEG ships without card data until the real deck is transcribed
+        assert!(data.cards.is_none());
+    }
+}
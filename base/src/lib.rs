@@ -1,4 +1,13 @@
 pub mod board;
+pub mod cards;
+pub mod dices;
+pub mod error;
+pub mod fingerprint;
 pub mod game;
 pub mod loader;
+pub mod pieces;
+pub mod registry;
+pub mod rule;
 pub mod validator;
+#[cfg(feature = "watch")]
+pub mod watch;
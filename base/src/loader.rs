@@ -1,24 +1,115 @@
 //! Generic loader for RON-based data structures.
 //!
+use crate::error::{DataError, Result};
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Serializes `value` as pretty RON and writes it to `path`.
+pub fn save_ron<T: Serialize>(value: &T, path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let text =
+        ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default()).map_err(|source| {
+            DataError::SerializeRon {
+                path: path.to_path_buf(),
+                source,
+            }
+        })?;
+    std::fs::write(path, text).map_err(|source| DataError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
 
 /// Loads and deserializes a RON file into the given type.
-/// Returns a Result<T, String>.
-pub fn load_ron<T: DeserializeOwned>(path: &str) -> Result<T, String> {
-    let file = File::open(path).map_err(|e| format!("Could not open file '{}': {}", path, e))?;
+/// I/O and parse failures are reported as distinct [`DataError`] variants.
+pub fn load_ron<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|source| DataError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
     let reader = BufReader::new(file);
-    ron::de::from_reader(reader).map_err(|e| format!("Failed to parse '{}': {}", path, e))
+    load_ron_reader(reader).map_err(|source| DataError::ParseRon {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Deserializes RON from any reader, e.g. `Cursor::new(bytes)` for
+/// embedded assets or in-memory fixtures.
+pub fn load_ron_reader<T: DeserializeOwned, R: Read>(
+    reader: R,
+) -> std::result::Result<T, ron::error::SpannedError> {
+    ron::de::from_reader(reader)
 }
 
 /// Loads, deserializes and validates a RON file.
-/// T must implement Validatable.
-pub fn load_and_validate_ron<T>(path: &str) -> Result<T, String>
+/// T must implement Validatable; its message is returned as `DataError::ValidationAt`
+/// when the check reports a location, otherwise as `DataError::Validation`.
+pub fn load_and_validate_ron<T>(path: impl AsRef<Path>) -> Result<T>
 where
     T: DeserializeOwned + crate::validator::Validatable,
 {
     let value = load_ron::<T>(path)?;
-    value.validate()?;
+    validate(&value)?;
     Ok(value)
 }
+
+/// Validates `value`, reporting a located failure as `DataError::ValidationAt`
+/// and any other as `DataError::Validation`.
+pub(crate) fn validate<T: crate::validator::Validatable>(value: &T) -> Result<()> {
+    value
+        .validate_located()
+        .map_err(|(message, location)| match location {
+            Some(location) => DataError::ValidationAt { message, location },
+            None => DataError::Validation(message),
+        })
+}
+
+/// Loads every `*.board.ron` file in `dir`, sorted by path.
+/// Each file gets its own result, so one broken board does not hide the others.
+pub fn load_board_catalog(dir: &Path) -> Result<Vec<(PathBuf, Result<crate::board::Board>)>> {
+    load_catalog(dir, ".board.ron")
+}
+
+/// Loads every `*.game.ron` file in `dir`, sorted by path.
+/// Each file gets its own result, so one broken game does not hide the others.
+pub fn load_game_catalog(
+    dir: &Path,
+) -> Result<Vec<(PathBuf, Result<crate::game::GameDefinition>)>> {
+    load_catalog(dir, ".game.ron")
+}
+
+/// Loads and validates all files in `dir` whose name ends with `suffix`.
+/// Only failing to read the directory itself is an error.
+fn load_catalog<T>(dir: &Path, suffix: &str) -> Result<Vec<(PathBuf, Result<T>)>>
+where
+    T: DeserializeOwned + crate::validator::Validatable,
+{
+    let io_error = |source| DataError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        let matches = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with(suffix));
+        if matches && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let value = load_and_validate_ron(&path);
+            (path, value)
+        })
+        .collect())
+}
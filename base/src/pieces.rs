@@ -1,24 +1,390 @@
 use serde::Deserialize;
+use std::path::Path;
 
-#[derive(Debug, Deserialize)]
+/// Top-level structure for piece definitions (`<name>.pieces.ron`).
+/// Each set is one box of pieces, typically one per player color.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct PiecesDefinition {
+    /// Unique identifier for this pieces definition.
     pub id: String,
+
+    /// Human-readable name of the pieces definition.
     pub name: String,
+
+    /// Author of the pieces definition.
     pub author: String,
+
+    /// Version string (semantic or otherwise).
     pub version: String,
+
+    /// Short description of the pieces.
     pub description: String,
+
+    /// Available piece sets (boxes).
     pub sets: Vec<PieceSet>,
 }
 
-#[derive(Debug, Deserialize)]
+/// A box of pieces in one color.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct PieceSet {
+    /// Numeric ID (must be unique).
     pub id: u8,
+
+    /// Name of the set (e.g. "Green").
     pub name: String,
+
+    /// Pieces in this set, one per denomination.
     pub pieces: Vec<Piece>,
 }
 
-#[derive(Debug, Deserialize)]
+/// A single piece, standing for `value` units on the board.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Piece {
+    /// Number of units the piece represents (unique per set).
     pub value: u8,
+
+    /// Image used to render the piece.
     pub image: String,
 }
+
+impl PiecesDefinition {
+    /// Loads and validates a pieces definition from a RON file.
+    pub fn from_file(path: impl AsRef<Path>) -> crate::error::Result<Self> {
+        crate::loader::load_and_validate_ron(path)
+    }
+
+    /// Looks up a piece set (box) by its id.
+    pub fn set(&self, set_id: u8) -> Option<&PieceSet> {
+        self.sets.iter().find(|set| set.id == set_id)
+    }
+
+    /// Sums the values of all pieces in the given set.
+    /// Returns `None` if the set does not exist.
+    pub fn set_total(&self, set_id: u8) -> Option<u32> {
+        self.set(set_id).map(PieceSet::total_value)
+    }
+
+    /// Expresses `amount` units with the pieces of the given set, greedily
+    /// taking the largest values first (e.g. 17 with `[1, 5, 10]` gives `[10, 5, 1, 1]`).
+    /// Returns `None` if the set does not exist or `amount` cannot be reached exactly.
+    /// Being greedy, it can miss sums that need a larger piece to be skipped.
+    pub fn decompose(&self, set_id: u8, amount: u32) -> Option<Vec<Piece>> {
+        let mut pieces: Vec<&Piece> = self
+            .set(set_id)?
+            .pieces
+            .iter()
+            .filter(|piece| piece.value > 0)
+            .collect();
+        pieces.sort_by_key(|piece| std::cmp::Reverse(piece.value));
+
+        let mut remaining = amount;
+        let mut result = Vec::new();
+        for piece in pieces {
+            let value = u32::from(piece.value);
+            for _ in 0..remaining / value {
+                result.push(piece.clone());
+            }
+            remaining %= value;
+        }
+        (remaining == 0).then_some(result)
+    }
+
+    /// Looks up the piece with the given value in a set.
+    /// Values are unique per set (see validation), so the lookup is unambiguous.
+    pub fn piece_for_value(&self, set_id: u8, value: u8) -> Option<&Piece> {
+        self.set(set_id)?
+            .pieces
+            .iter()
+            .find(|piece| piece.value == value)
+    }
+
+    /// Combines this definition with an add-on, keeping this one's metadata.
+    /// Sets with the same id have their pieces concatenated; the result is
+    /// validated again, so a value present in both fails as a duplicate.
+    pub fn merge(&self, other: &PiecesDefinition) -> Result<PiecesDefinition, String> {
+        let mut merged = self.clone();
+        for set in &other.sets {
+            match merged.sets.iter_mut().find(|own| own.id == set.id) {
+                Some(own) => own.pieces.extend(set.pieces.iter().cloned()),
+                None => merged.sets.push(set.clone()),
+            }
+        }
+        crate::validator::Validatable::validate(&merged)?;
+        Ok(merged)
+    }
+
+    /// Checks that every piece image exists as a file below `base_dir`.
+    /// Kept apart from `validate()` so callers without assets on disk can skip it.
+    /// The first missing image is reported with its resolved path.
+    pub fn validate_images<P: AsRef<Path>>(&self, base_dir: P) -> crate::error::Result<()> {
+        for set in &self.sets {
+            for piece in &set.pieces {
+                let path = base_dir.as_ref().join(&piece.image);
+                if !path.is_file() {
+                    return Err(crate::error::DataError::AssetNotFound(path));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Sums the values of all pieces across all sets.
+    pub fn total_value(&self) -> u32 {
+        self.sets.iter().map(PieceSet::total_value).sum()
+    }
+
+    /// Exports all pieces as CSV with the header `box_id,box_name,value,image`,
+    /// one row per piece, sorted by box id and then by value.
+    pub fn to_csv(&self) -> String {
+        let mut rows: Vec<(u8, &str, u8, &str)> = self
+            .sets
+            .iter()
+            .flat_map(|set| {
+                set.pieces.iter().map(move |piece| {
+                    (set.id, set.name.as_str(), piece.value, piece.image.as_str())
+                })
+            })
+            .collect();
+        rows.sort();
+
+        let mut csv = String::from(CSV_HEADER);
+        csv.push('\n');
+        for (id, name, value, image) in rows {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                id,
+                csv_quote(name),
+                value,
+                csv_quote(image)
+            ));
+        }
+        csv
+    }
+
+    /// Imports pieces from CSV as written by [`PiecesDefinition::to_csv`].
+    /// `name` becomes both id and name of the definition; boxes keep the ids
+    /// from the `box_id` column, so lookups by set id still work afterwards.
+    /// The result is validated.
+    pub fn from_csv(name: &str, csv: &str) -> Result<PiecesDefinition, String> {
+        let mut definition = PiecesDefinition {
+            id: name.to_string(),
+            name: name.to_string(),
+            author: String::new(),
+            version: String::new(),
+            description: String::new(),
+            sets: Vec::new(),
+        };
+        for (index, (line, columns)) in csv_records(csv)?.into_iter().enumerate() {
+            if index == 0 && columns.join(",") == CSV_HEADER {
+                continue;
+            }
+            let [box_id, box_name, value, image] = columns.as_slice() else {
+                return Err(format!(
+                    "Pieces: CSV line {} needs 4 columns, found {}",
+                    line,
+                    columns.len()
+                ));
+            };
+            let parse = |column: &str, text: &str| {
+                text.trim().parse::<u8>().map_err(|e| {
+                    format!(
+                        "Pieces: CSV line {} has invalid {} '{}': {}",
+                        line, column, text, e
+                    )
+                })
+            };
+            let box_id = parse("box_id", box_id)?;
+            let value = parse("value", value)?;
+            let set = match definition.sets.iter().position(|set| set.id == box_id) {
+                Some(position) => &mut definition.sets[position],
+                None => {
+                    definition.sets.push(PieceSet {
+                        id: box_id,
+                        name: box_name.clone(),
+                        pieces: Vec::new(),
+                    });
+                    definition.sets.last_mut().expect("set was just pushed")
+                }
+            };
+            if &set.name != box_name {
+                return Err(format!(
+                    "Pieces: CSV line {} names box {} '{}', earlier rows '{}'",
+                    line, box_id, box_name, set.name
+                ));
+            }
+            set.pieces.push(Piece {
+                value,
+                image: image.clone(),
+            });
+        }
+        crate::validator::Validatable::validate(&definition)?;
+        Ok(definition)
+    }
+}
+
+impl PieceSet {
+    /// Sums the values of all pieces in this set.
+    pub fn total_value(&self) -> u32 {
+        self.pieces.iter().map(|piece| u32::from(piece.value)).sum()
+    }
+}
+
+impl crate::validator::Validatable for PiecesDefinition {
+    fn validate(&self) -> Result<(), String> {
+        if self.id.trim().is_empty() {
+            return Err("Pieces: 'id' must not be empty.".into());
+        }
+        // Check for duplicate set IDs
+        let mut ids = std::collections::HashSet::new();
+        for set in &self.sets {
+            if !ids.insert(set.id) {
+                return Err(format!("Pieces: duplicate set id {}", set.id));
+            }
+            // Check for duplicate piece values within the set
+            let mut values = std::collections::HashSet::new();
+            for piece in &set.pieces {
+                if !values.insert(piece.value) {
+                    return Err(format!(
+                        "Pieces: set {} has duplicate value {}",
+                        set.id, piece.value
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+const CSV_HEADER: &str = "box_id,box_name,value,image";
+
+/// Quotes a CSV field if it contains a comma, quote or line break.
+fn csv_quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Splits CSV text into records of fields, honoring quoted fields with `""`
+/// escapes and embedded line breaks. Blank lines are skipped. Each record
+/// carries the 1-based line it starts on.
+fn csv_records(csv: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                fields.push(std::mem::take(&mut field));
+                if fields != [""] {
+                    records.push((start, std::mem::take(&mut fields)));
+                }
+                fields.clear();
+                line += 1;
+                start = line;
+            }
+            _ => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    if quoted {
+        return Err(format!(
+            "Pieces: CSV line {} has an unterminated quote",
+            start
+        ));
+    }
+    fields.push(field);
+    if fields != [""] {
+        records.push((start, fields));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(values: &[u8]) -> PiecesDefinition {
+        PiecesDefinition {
+            id: "test".into(),
+            name: "Test".into(),
+            author: String::new(),
+            version: String::new(),
+            description: String::new(),
+            sets: vec![PieceSet {
+                id: 1,
+                name: "Green".into(),
+                pieces: values
+                    .iter()
+                    .map(|&value| Piece {
+                        value,
+                        image: format!("green_{}.png", value),
+                    })
+                    .collect(),
+            }],
+        }
+    }
+
+    fn values(pieces: &[Piece]) -> Vec<u8> {
+        pieces.iter().map(|piece| piece.value).collect()
+    }
+
+    #[test]
+    fn decompose_takes_largest_pieces_first() {
+        let pieces = definition(&[1, 5, 10]).decompose(1, 17).unwrap();
+        assert_eq!(values(&pieces), vec![10, 5, 1, 1]);
+    }
+
+    #[test]
+    fn missing_image_reports_resolved_path() {
+        let base_dir = std::env::temp_dir();
+        let err = definition(&[1]).validate_images(&base_dir).unwrap_err();
+        match err {
+            crate::error::DataError::AssetNotFound(path) => {
+                assert_eq!(path, base_dir.join("green_1.png"));
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn csv_round_trip_keeps_set_ids() {
+        let original: PiecesDefinition =
+            ron::from_str(include_str!("../../wad/game/teg.pieces.ron")).unwrap();
+        let imported = PiecesDefinition::from_csv("teg", &original.to_csv()).unwrap();
+        for set in &original.sets {
+            let copy = imported.set(set.id).unwrap();
+            assert_eq!(copy.name, set.name);
+            assert_eq!(values(&copy.pieces), values(&set.pieces));
+        }
+        assert_eq!(imported.to_csv(), original.to_csv());
+    }
+
+    #[test]
+    fn csv_reads_back_quoted_commas_quotes_and_newlines() {
+        let mut original = definition(&[1, 5]);
+        original.sets[0].name = "Green, \"dark\"\nedition".into();
+        let imported = PiecesDefinition::from_csv("test", &original.to_csv()).unwrap();
+        assert_eq!(imported.sets[0].name, original.sets[0].name);
+        assert_eq!(values(&imported.sets[0].pieces), vec![1, 5]);
+    }
+}
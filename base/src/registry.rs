@@ -0,0 +1,82 @@
+//! Cache for loaded data files, shared via `Arc`.
+//!
+use crate::board::Board;
+use crate::error::{DataError, Result};
+use crate::game::GameDefinition;
+use crate::loader::load_and_validate_ron;
+use crate::validator::Validatable;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Keeps loaded boards and game definitions by canonical path, so each
+/// file is parsed and validated only once. Safe to share between threads.
+#[derive(Debug, Default)]
+pub struct DataRegistry {
+    boards: Mutex<HashMap<PathBuf, Arc<Board>>>,
+    games: Mutex<HashMap<PathBuf, Arc<GameDefinition>>>,
+}
+
+impl DataRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the board at `path`, loading and validating it on first use.
+    pub fn load_board(&self, path: impl AsRef<Path>) -> Result<Arc<Board>> {
+        cached(&self.boards, path.as_ref())
+    }
+
+    /// Returns the game definition at `path`, loading and validating it on first use.
+    pub fn load_game(&self, path: impl AsRef<Path>) -> Result<Arc<GameDefinition>> {
+        cached(&self.games, path.as_ref())
+    }
+
+    /// Drops any cached data for `path`, so the next load reads the file again.
+    /// Handles already given out stay valid.
+    pub fn invalidate(&self, path: impl AsRef<Path>) {
+        let Ok(key) = canonical(path.as_ref()) else {
+            return;
+        };
+        lock(&self.boards).remove(&key);
+        lock(&self.games).remove(&key);
+    }
+
+    /// Drops all cached data.
+    pub fn clear(&self) {
+        lock(&self.boards).clear();
+        lock(&self.games).clear();
+    }
+}
+
+fn cached<T>(cache: &Mutex<HashMap<PathBuf, Arc<T>>>, path: &Path) -> Result<Arc<T>>
+where
+    T: DeserializeOwned + Validatable,
+{
+    let key = canonical(path)?;
+    if let Some(value) = lock(cache).get(&key) {
+        return Ok(Arc::clone(value));
+    }
+    // Load without holding the lock; a concurrent load of the same file
+    // simply ends up replacing an equal entry.
+    let value = Arc::new(load_and_validate_ron::<T>(&key)?);
+    lock(cache).insert(key, Arc::clone(&value));
+    Ok(value)
+}
+
+fn canonical(path: &Path) -> Result<PathBuf> {
+    std::fs::canonicalize(path).map_err(|source| DataError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Locks the cache, recovering from a poisoned lock since the map is never
+/// left half-updated.
+fn lock<K, V>(cache: &Mutex<HashMap<K, V>>) -> std::sync::MutexGuard<'_, HashMap<K, V>> {
+    cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
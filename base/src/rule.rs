@@ -1,8 +1,10 @@
-use serde::Deserialize;
-use std::collections::HashMap;
+use crate::board::Board;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Top-level structure representing a complete rule definition file
-#[derive(Debug, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct RuleDefinition {
     /// Unique identifier for the rule set (e.g. "teg")
     pub id: String,
@@ -19,13 +21,15 @@ pub struct RuleDefinition {
     /// Configurable parameters and setup behavior
     pub parameters: RuleParameters,
     /// Phase definition with allowed actions and transitions
+    #[serde(serialize_with = "sorted_map")]
     pub phases: HashMap<String, PhaseDefinition>,
     /// List of possible goals that can be assigned to players
     pub goals: Vec<GoalDefinition>,
 }
 
 /// Holds tunable parameters affecting game setup and rules
-#[derive(Debug, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct RuleParameters {
     /// Name of the initial phase
     pub default_phase: String,
@@ -39,10 +43,14 @@ pub struct RuleParameters {
     pub placement: PlacementConfig,
     /// Sequence of bonus figures for card trades (increasing)
     pub card_bonus_sequence: Vec<u8>,
+    /// Phases in which the game legitimately ends (optional)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub terminal_phases: Vec<String>,
 }
 
 /// Figure placement settings
-#[derive(Debug, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct PlacementConfig {
     /// Number of figures per player in the setup round
     pub setup_round_figures: u8,
@@ -55,25 +63,31 @@ pub struct PlacementConfig {
 }
 
 /// Describes a game phase, which may contain multiple actions
-#[derive(Debug, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct PhaseDefinition {
     /// Mapping of action names to definitions (e.g. "place_figure": …)
-    #[serde(flatten)]
+    #[serde(flatten, serialize_with = "sorted_map")]
     pub actions: HashMap<String, ActionDefinition>,
 }
 
 /// Defines what happens when an action is executed in a phase
-#[derive(Debug, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ActionDefinition {
     /// Mapping from result strings to next phase names
+    #[serde(serialize_with = "sorted_map")]
     pub result: HashMap<String, String>,
     /// Optional constraints (number, boolean, string values)
-    #[serde(default)]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "sorted_optional_map"
+    )]
     pub constraints: Option<HashMap<String, ConstraintValue>>,
 }
 
 /// Flexible enum to represent different constraint types
-#[derive(Debug, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ConstraintValue {
     /// Boolean constraint (e.g. true/false)
@@ -84,38 +98,419 @@ pub enum ConstraintValue {
     String(String),
 }
 
+impl ConstraintValue {
+    /// Returns the kind of value held by this constraint.
+    pub fn kind(&self) -> ConstraintKind {
+        match self {
+            ConstraintValue::Bool(_) => ConstraintKind::Bool,
+            ConstraintValue::Number(_) => ConstraintKind::Number,
+            ConstraintValue::String(_) => ConstraintKind::String,
+        }
+    }
+}
+
+/// Kind of value a constraint is expected to hold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintKind {
+    Bool,
+    Number,
+    String,
+}
+
+/// Constraints known to the engine, with the kind of value each expects
+pub const CONSTRAINT_SCHEMA: &[(&str, ConstraintKind)] = &[
+    ("adjacency_required", ConstraintKind::Bool),
+    ("conquest_required", ConstraintKind::Bool),
+    ("encounter_required", ConstraintKind::Bool),
+    ("encounter_threshold", ConstraintKind::Number),
+    ("fieldsets_bonus", ConstraintKind::Bool),
+    ("from_cards", ConstraintKind::Bool),
+    ("from_fields", ConstraintKind::Bool),
+    ("from_zones", ConstraintKind::Bool),
+    ("max_dice", ConstraintKind::Number),
+    ("max_move", ConstraintKind::Number),
+    ("min_leave", ConstraintKind::Number),
+    ("min_move", ConstraintKind::Number),
+    ("min_origin_figures", ConstraintKind::Number),
+    ("min_players", ConstraintKind::Number),
+    ("only_adjacent", ConstraintKind::Bool),
+    ("total_per_player", ConstraintKind::Number),
+];
+
 /// Defines a player goal – either simple or with fallback logic
-#[derive(Debug, Deserialize)]
+///
+/// `WithFallback` is listed first: untagged variants are tried in order and
+/// `Simple` would otherwise also accept a goal carrying a `fallback_goal`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum GoalDefinition {
+    /// Goal with a fallback (e.g. if remove fails, control total)
+    WithFallback {
+        name: String,
+        #[serde(rename = "type")]
+        goal_type: String,
+        target_player: u8,
+        fallback_goal: FallbackGoal,
+    },
     /// Simple goal without fallback
     Simple {
         name: String,
         #[serde(rename = "type")]
         goal_type: String,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         sets: Option<Vec<u8>>,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         min_total_figures: Option<u8>,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         field_count: Option<u8>,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         target_player: Option<u8>,
     },
-    /// Goal with a fallback (e.g. if remove fails, control total)
-    WithFallback {
-        name: String,
-        #[serde(rename = "type")]
-        goal_type: String,
-        target_player: u8,
-        fallback_goal: FallbackGoal,
-    },
 }
 
 /// Fallback goal definition (used inside `WithFallback`)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FallbackGoal {
     #[serde(rename = "type")]
     pub goal_type: String,
     pub field_count: u8,
 }
+
+impl RuleDefinition {
+    /// Loads and validates a rule definition from a RON file.
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> crate::error::Result<Self> {
+        crate::loader::load_and_validate_ron(path)
+    }
+
+    /// Writes the rule definition to a RON file in pretty format.
+    pub fn to_file(&self, path: impl AsRef<std::path::Path>) -> crate::error::Result<()> {
+        crate::loader::save_ron(self, path)
+    }
+
+    /// Collects every phase reachable from `default_phase` by following
+    /// action results. Only phases defined in `phases` are included.
+    pub fn reachable_phases(&self) -> HashSet<String> {
+        let mut reachable = HashSet::new();
+        let mut pending = vec![&self.parameters.default_phase];
+        while let Some(name) = pending.pop() {
+            let Some(phase) = self.phases.get(name) else {
+                continue;
+            };
+            if !reachable.insert(name.clone()) {
+                continue;
+            }
+            for action in phase.actions.values() {
+                pending.extend(action.result.values());
+            }
+        }
+        reachable
+    }
+
+    /// Returns all defined phases that cannot be reached from `default_phase`.
+    pub fn unreachable_phases(&self) -> HashSet<String> {
+        let reachable = self.reachable_phases();
+        self.phases
+            .keys()
+            .filter(|name| !reachable.contains(*name))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the phases the game can never leave, sorted by name: phases
+    /// without actions, and phases whose action results all lead back to
+    /// themselves. Phases listed in `terminal_phases` are intended ends and
+    /// are not reported.
+    pub fn dead_end_phases(&self) -> Vec<String> {
+        let mut dead_ends: Vec<String> = self
+            .phases
+            .iter()
+            .filter(|(name, _)| !self.parameters.terminal_phases.contains(name))
+            .filter(|(name, phase)| {
+                phase
+                    .actions
+                    .values()
+                    .flat_map(|action| action.result.values())
+                    .all(|target| target == *name)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        dead_ends.sort();
+        dead_ends
+    }
+
+    /// Checks every action's constraints against [`CONSTRAINT_SCHEMA`],
+    /// rejecting unknown constraint names and values of the wrong kind.
+    /// Kept apart from `validate()` so rule sets for engines with additional
+    /// constraints still load.
+    pub fn validate_constraints(&self) -> Result<(), String> {
+        let mut phases: Vec<_> = self.phases.iter().collect();
+        phases.sort_by_key(|(name, _)| *name);
+        for (_, phase) in phases {
+            let mut actions: Vec<_> = phase.actions.iter().collect();
+            actions.sort_by_key(|(name, _)| *name);
+            for (action_name, action) in actions {
+                let mut constraints: Vec<_> = action.constraints.iter().flatten().collect();
+                constraints.sort_by_key(|(name, _)| *name);
+                for (name, value) in constraints {
+                    let Some(&(_, expected)) =
+                        CONSTRAINT_SCHEMA.iter().find(|(known, _)| known == name)
+                    else {
+                        return Err(format!(
+                            "Rule: unknown constraint '{}' in action '{}'",
+                            name, action_name
+                        ));
+                    };
+                    if value.kind() != expected {
+                        return Err(format!(
+                            "Rule: constraint '{}' in action '{}' expects {:?}, found {:?}",
+                            name,
+                            action_name,
+                            expected,
+                            value.kind()
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the goals against the board the rules are played on:
+    /// every referenced set id must exist in `board.sets`, and every
+    /// `target_player` must lie within `0..max_players`.
+    pub fn validate_against_board(&self, board: &Board) -> Result<(), String> {
+        for goal in &self.goals {
+            let (name, sets, target_player) = match goal {
+                GoalDefinition::WithFallback {
+                    name,
+                    target_player,
+                    ..
+                } => (name, None, Some(*target_player)),
+                GoalDefinition::Simple {
+                    name,
+                    sets,
+                    target_player,
+                    ..
+                } => (name, sets.as_ref(), *target_player),
+            };
+            for &set_id in sets.into_iter().flatten() {
+                if !board.sets.iter().any(|set| set.id == set_id) {
+                    return Err(format!(
+                        "Rule: goal '{}' references unknown set id {}",
+                        name, set_id
+                    ));
+                }
+            }
+            if let Some(player) = target_player {
+                if player >= self.parameters.max_players {
+                    return Err(format!(
+                        "Rule: goal '{}' targets player {}, outside 0..{}",
+                        name, player, self.parameters.max_players
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl crate::validator::Validatable for RuleDefinition {
+    fn validate(&self) -> Result<(), String> {
+        if self.id.trim().is_empty() {
+            return Err("Rule: 'id' must not be empty.".into());
+        }
+        if self.name.trim().is_empty() {
+            return Err("Rule: 'name' must not be empty.".into());
+        }
+        let params = &self.parameters;
+        if params.min_players > params.max_players {
+            return Err(format!(
+                "Rule: min_players ({}) must not exceed max_players ({})",
+                params.min_players, params.max_players
+            ));
+        }
+        if params.card_bonus_sequence.is_empty() {
+            return Err("Rule: card_bonus_sequence must not be empty".into());
+        }
+        if params.card_bonus_sequence.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(format!(
+                "Rule: card_bonus_sequence must be strictly increasing, found {:?}",
+                params.card_bonus_sequence
+            ));
+        }
+        if !self.phases.contains_key(&params.default_phase) {
+            return Err(format!(
+                "Rule: default_phase '{}' is not a defined phase",
+                params.default_phase
+            ));
+        }
+        for terminal in &params.terminal_phases {
+            if !self.phases.contains_key(terminal) {
+                return Err(format!(
+                    "Rule: terminal phase '{}' is not a defined phase",
+                    terminal
+                ));
+            }
+        }
+        // Check that every action result leads to a defined phase.
+        // Iterate in sorted order so the reported error is stable.
+        let mut phases: Vec<_> = self.phases.iter().collect();
+        phases.sort_by_key(|(name, _)| *name);
+        for (phase_name, phase) in phases {
+            let mut actions: Vec<_> = phase.actions.iter().collect();
+            actions.sort_by_key(|(name, _)| *name);
+            for (action_name, action) in actions {
+                let mut results: Vec<_> = action.result.iter().collect();
+                results.sort();
+                for (result, target) in results {
+                    if !self.phases.contains_key(target) {
+                        return Err(format!(
+                            "Rule: action '{}' in phase '{}' leads to unknown phase '{}' on result '{}'",
+                            action_name, phase_name, target, result
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Serializes a map with its keys in sorted order, so written rule files
+/// are stable across runs and diff cleanly.
+fn sorted_map<S, V>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    V: Serialize,
+{
+    serializer.collect_map(map.iter().collect::<BTreeMap<_, _>>())
+}
+
+/// Like [`sorted_map`], for optional maps.
+fn sorted_optional_map<S, V>(
+    map: &Option<HashMap<String, V>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    V: Serialize,
+{
+    match map {
+        Some(map) => serializer.serialize_some(&map.iter().collect::<BTreeMap<_, _>>()),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::Validatable;
+
+    fn teg_rules() -> RuleDefinition {
+        ron::from_str(include_str!("../../wad/game/teg.rule.ron")).unwrap()
+    }
+
+    fn with_bonus_sequence(sequence: Vec<u8>) -> Result<(), String> {
+        let mut rules = teg_rules();
+        rules.parameters.card_bonus_sequence = sequence;
+        rules.validate()
+    }
+
+    #[test]
+    fn teg_rules_are_valid() {
+        assert_eq!(teg_rules().validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_empty_bonus_sequence() {
+        assert_eq!(
+            with_bonus_sequence(vec![]),
+            Err("Rule: card_bonus_sequence must not be empty".into())
+        );
+    }
+
+    #[test]
+    fn rejects_equal_adjacent_bonuses() {
+        assert_eq!(
+            with_bonus_sequence(vec![4, 7, 7, 10]),
+            Err(
+                "Rule: card_bonus_sequence must be strictly increasing, found [4, 7, 7, 10]".into()
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_decreasing_bonuses() {
+        assert_eq!(
+            with_bonus_sequence(vec![10, 7, 4]),
+            Err("Rule: card_bonus_sequence must be strictly increasing, found [10, 7, 4]".into())
+        );
+    }
+
+    fn round_trip<T>(value: &T) -> T
+    where
+        T: Serialize + serde::de::DeserializeOwned,
+    {
+        ron::from_str(&ron::to_string(value).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn teg_rules_round_trip() {
+        let rules = teg_rules();
+        assert_eq!(round_trip(&rules), rules);
+    }
+
+    #[test]
+    fn goal_with_fallback_is_not_read_as_simple() {
+        let goal: GoalDefinition = ron::from_str(
+            r#"(name: "destroy", type: "remove_player", target_player: 1,
+                fallback_goal: (type: "control_fields", field_count: 24))"#,
+        )
+        .unwrap();
+        assert!(matches!(goal, GoalDefinition::WithFallback { .. }));
+        assert_eq!(round_trip(&goal), goal);
+    }
+
+    #[test]
+    fn simple_goal_round_trips() {
+        let goal: GoalDefinition =
+            ron::from_str(r#"(name: "world", type: "control_fields", field_count: 30)"#).unwrap();
+        assert!(matches!(
+            goal,
+            GoalDefinition::Simple {
+                field_count: Some(30),
+                sets: None,
+                ..
+            }
+        ));
+        assert_eq!(round_trip(&goal), goal);
+    }
+
+    #[test]
+    fn constraint_values_round_trip() {
+        for value in [
+            ConstraintValue::Bool(true),
+            ConstraintValue::Number(3),
+            ConstraintValue::String("turn".into()),
+        ] {
+            assert_eq!(round_trip(&value), value);
+        }
+    }
+
+    #[test]
+    fn dead_ends_skip_terminal_phases() {
+        let mut rules = teg_rules();
+        assert!(rules.dead_end_phases().is_empty());
+        rules.phases.insert(
+            "game_over".into(),
+            PhaseDefinition {
+                actions: HashMap::new(),
+            },
+        );
+        assert_eq!(rules.dead_end_phases(), vec!["game_over".to_string()]);
+        rules.parameters.terminal_phases = vec!["game_over".into()];
+        assert!(rules.dead_end_phases().is_empty());
+        assert_eq!(rules.validate(), Ok(()));
+    }
+}
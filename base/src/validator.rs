@@ -4,4 +4,10 @@ pub trait Validatable {
     /// Checks if the data is valid and consistent.
     /// Returns Ok(()) on success, or Err(String) with error message.
     fn validate(&self) -> Result<(), String>;
+
+    /// Like `validate`, but also reports where the problem is when known.
+    /// The default reports no location.
+    fn validate_located(&self) -> Result<(), (String, Option<crate::error::Location>)> {
+        self.validate().map_err(|message| (message, None))
+    }
 }
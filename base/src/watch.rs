@@ -0,0 +1,94 @@
+//! Hot-reloading of RON data files (feature `watch`).
+//!
+use crate::error::Result;
+use crate::loader::load_and_validate_ron;
+use crate::validator::Validatable;
+use serde::de::DeserializeOwned;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+/// How often the file's modification time is checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long the modification time must stay unchanged before reloading,
+/// so an editor writing a file in several steps triggers a single reload.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Handle to a running watcher; dropping it stops the watch thread.
+#[derive(Debug)]
+pub struct RonWatcher {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RonWatcher {
+    /// Stops watching and waits for the watch thread to finish.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for RonWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Watches a RON file and calls `callback` with a freshly loaded and validated
+/// `T` every time the file changes. Load and validation failures are handed
+/// to the callback as well, so a broken intermediate save does not end the watch.
+///
+/// Changes are detected by polling the modification time on a background thread.
+pub fn watch_ron<T, F>(path: impl AsRef<Path>, mut callback: F) -> RonWatcher
+where
+    T: DeserializeOwned + Validatable,
+    F: FnMut(Result<T>) + Send + 'static,
+{
+    let path = path.as_ref().to_path_buf();
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread = {
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let mut last_seen = modified(&path);
+            while !stop.load(Ordering::Relaxed) {
+                thread::sleep(POLL_INTERVAL);
+                let current = modified(&path);
+                if current == last_seen {
+                    continue;
+                }
+                // Wait until writes have settled
+                let mut settled = current;
+                loop {
+                    thread::sleep(DEBOUNCE);
+                    let latest = modified(&path);
+                    if latest == settled || stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    settled = latest;
+                }
+                last_seen = settled;
+                callback(load_and_validate_ron(&path));
+            }
+        })
+    };
+    RonWatcher {
+        stop,
+        thread: Some(thread),
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
@@ -1,13 +1,12 @@
-use base::board::Board;
-use base::game::GameDefinition;
+use base::game::{load_game, GameDefinition};
 use base::loader::load_and_validate_ron;
+use std::path::Path;
 
 fn main() {
     let game = load_and_validate_ron::<GameDefinition>("wad/game/teg.game.ron")
         .expect("Failed to load or validate game");
     println!("Game loaded and validated: {:#?}", game);
 
-    let board = load_and_validate_ron::<Board>("wad/game/teg.board.ron")
-        .expect("Failed to load or validate board");
-    println!("Board loaded and validated: {:?}", board);
+    let data = load_game(&game, Path::new("wad/game")).expect("Failed to load game components");
+    println!("Board loaded and validated: {:?}", data.board);
 }